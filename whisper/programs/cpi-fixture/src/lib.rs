@@ -0,0 +1,90 @@
+// ============================================
+// FILE: programs/cpi-fixture/src/lib.rs
+// ============================================
+// Test-only program: creates a confession through CPI and checks the
+// `CreatedAccount` whisper leaves in the return data. Never deployed
+// outside the local validator.
+#![allow(deprecated)]
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::get_return_data;
+use whisper::program::Whisper;
+use whisper::{CreatedAccount, GlobalStats};
+
+declare_id!("A6VBgYscTh4MPerf2s8XY5YzC4RyxnxaYs1c9NvaFydZ");
+
+#[program]
+pub mod cpi_fixture {
+    use super::*;
+
+    /// Publishes a confession for `author` through whisper, then fails
+    /// unless the return data names that confession and the global index
+    /// `GlobalStats` was about to hand out.
+    pub fn create_via_cpi(ctx: Context<CreateViaCpi>, content_uri: String) -> Result<()> {
+        let expected_index = ctx.accounts.stats.next_confession_index;
+
+        let accounts = whisper::cpi::accounts::CreateConfession {
+            confession: ctx.accounts.confession.to_account_info(),
+            stats: ctx.accounts.stats.to_account_info(),
+            author: ctx.accounts.author.to_account_info(),
+            payer: ctx.accounts.payer.to_account_info(),
+            quoted_comment: None,
+            quoted_confession: None,
+            system_program: ctx.accounts.system_program.to_account_info(),
+        };
+        whisper::cpi::create_confession(
+            CpiContext::new(ctx.accounts.whisper_program.to_account_info(), accounts),
+            content_uri,
+            None,
+            true,
+            None,
+            None,
+            0,
+            0,
+        )?;
+
+        let (program_id, data) = get_return_data().ok_or(FixtureError::MissingReturnData)?;
+        require_keys_eq!(program_id, whisper::ID, FixtureError::ReturnDataMismatch);
+        let created = CreatedAccount::try_from_slice(&data)?;
+        require_keys_eq!(
+            created.address,
+            ctx.accounts.confession.key(),
+            FixtureError::ReturnDataMismatch
+        );
+        require!(
+            created.index == Some(expected_index),
+            FixtureError::ReturnDataMismatch
+        );
+
+        msg!("Return data checked: index {}", expected_index);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CreateViaCpi<'info> {
+    /// CHECK: created by whisper, which checks its seeds.
+    #[account(mut)]
+    pub confession: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub stats: Account<'info, GlobalStats>,
+
+    pub author: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub whisper_program: Program<'info, Whisper>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[error_code]
+pub enum FixtureError {
+    #[msg("whisper set no return data")]
+    MissingReturnData,
+
+    #[msg("Return data does not match the created confession")]
+    ReturnDataMismatch,
+}
@@ -0,0 +1,421 @@
+//! Account layout registry for indexers, built with the `client` feature.
+//!
+//! Each account type gets its discriminator, total size and a table of
+//! `(name, offset, len)` entries in declaration order. The tests below check
+//! every table against real Borsh serialization, so a moved field fails the
+//! build instead of silently breaking memcmp filters. They are the only
+//! tests of the `*_OFFSET` constants, which the tables are built from.
+
+use anchor_lang::Discriminator;
+
+use crate::{
+    CommentAccount, ConfessionAccount, GlobalStats, Snapshot, TipLedger, UserCounter, MAX_COAUTHORS,
+};
+
+/// One serialized field. `offset` includes the discriminator and is `None`
+/// when the position depends on an earlier variable-width `Option`; `len` is
+/// the width of the `Some` case for `Option` fields.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FieldLayout {
+    pub name: &'static str,
+    pub offset: Option<usize>,
+    pub len: usize,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct AccountLayout {
+    pub name: &'static str,
+    pub discriminator: &'static [u8],
+    pub size: usize,
+    pub fields: &'static [FieldLayout],
+}
+
+impl AccountLayout {
+    /// Looks up a field's fixed offset and length by name.
+    pub fn field(&self, name: &str) -> Option<FieldLayout> {
+        self.fields.iter().copied().find(|f| f.name == name)
+    }
+}
+
+const fn at(name: &'static str, offset: usize, len: usize) -> FieldLayout {
+    FieldLayout {
+        name,
+        offset: Some(offset),
+        len,
+    }
+}
+
+const fn after_option(name: &'static str, len: usize) -> FieldLayout {
+    FieldLayout {
+        name,
+        offset: None,
+        len,
+    }
+}
+
+pub const CONFESSION_ACCOUNT: AccountLayout = AccountLayout {
+    name: "ConfessionAccount",
+    discriminator: ConfessionAccount::DISCRIMINATOR,
+    size: ConfessionAccount::SPACE,
+    fields: &[
+        at("author", ConfessionAccount::AUTHOR_OFFSET, 32),
+        at(
+            "uri",
+            ConfessionAccount::URI_OFFSET,
+            ConfessionAccount::MAX_URI_LENGTH,
+        ),
+        at("uri_len", ConfessionAccount::URI_LEN_OFFSET, 2),
+        at("like_count", ConfessionAccount::LIKE_COUNT_OFFSET, 8),
+        at("comment_count", ConfessionAccount::COMMENT_COUNT_OFFSET, 8),
+        at("timestamp", ConfessionAccount::TIMESTAMP_OFFSET, 8),
+        at("slot", ConfessionAccount::SLOT_OFFSET, 8),
+        at("status", ConfessionAccount::STATUS_OFFSET, 1),
+        at("published_at", ConfessionAccount::PUBLISHED_AT_OFFSET, 8),
+        at("tip_events", ConfessionAccount::TIP_EVENTS_OFFSET, 4),
+        at(
+            "comments_frozen_until",
+            ConfessionAccount::COMMENTS_FROZEN_UNTIL_OFFSET,
+            8,
+        ),
+        at(
+            "tip_goal_lamports",
+            ConfessionAccount::TIP_GOAL_LAMPORTS_OFFSET,
+            8,
+        ),
+        at(
+            "goal_reached_at",
+            ConfessionAccount::GOAL_REACHED_AT_OFFSET,
+            8,
+        ),
+        at("scheduled_for", ConfessionAccount::SCHEDULED_FOR_OFFSET, 8),
+        at("rent_payer", ConfessionAccount::RENT_PAYER_OFFSET, 32),
+        at(
+            "coauthors",
+            ConfessionAccount::COAUTHORS_OFFSET,
+            32 * MAX_COAUTHORS,
+        ),
+        at(
+            "checkin_interval",
+            ConfessionAccount::CHECKIN_INTERVAL_OFFSET,
+            8,
+        ),
+        at(
+            "release_deadline",
+            ConfessionAccount::RELEASE_DEADLINE_OFFSET,
+            8,
+        ),
+        at(
+            "original_author",
+            ConfessionAccount::ORIGINAL_AUTHOR_OFFSET,
+            32,
+        ),
+        at(
+            "content_warnings",
+            ConfessionAccount::CONTENT_WARNINGS_OFFSET,
+            2,
+        ),
+        at(
+            "content_standard",
+            ConfessionAccount::CONTENT_STANDARD_OFFSET,
+            1,
+        ),
+        at(
+            "last_activity_at",
+            ConfessionAccount::LAST_ACTIVITY_AT_OFFSET,
+            8,
+        ),
+        at(
+            "activity_window_start",
+            ConfessionAccount::ACTIVITY_WINDOW_START_OFFSET,
+            8,
+        ),
+        at(
+            "activity_count_24h",
+            ConfessionAccount::ACTIVITY_COUNT_24H_OFFSET,
+            4,
+        ),
+        at("bump", ConfessionAccount::BUMP_OFFSET, 1),
+        at(
+            "latest_comment",
+            ConfessionAccount::LATEST_COMMENT_OFFSET,
+            33,
+        ),
+        after_option("quoted_comment", 33),
+    ],
+};
+
+pub const COMMENT_ACCOUNT: AccountLayout = AccountLayout {
+    name: "CommentAccount",
+    discriminator: CommentAccount::DISCRIMINATOR,
+    size: CommentAccount::SPACE,
+    fields: &[
+        at("confession", CommentAccount::CONFESSION_OFFSET, 32),
+        at("commenter", CommentAccount::COMMENTER_OFFSET, 32),
+        at(
+            "uri",
+            CommentAccount::URI_OFFSET,
+            CommentAccount::MAX_URI_LENGTH,
+        ),
+        at("uri_len", CommentAccount::URI_LEN_OFFSET, 2),
+        at("timestamp", CommentAccount::TIMESTAMP_OFFSET, 8),
+        at("slot", CommentAccount::SLOT_OFFSET, 8),
+        at("quote_count", CommentAccount::QUOTE_COUNT_OFFSET, 4),
+        at("deleted", CommentAccount::DELETED_OFFSET, 1),
+        at("rent_payer", CommentAccount::RENT_PAYER_OFFSET, 32),
+        at("total_tips", CommentAccount::TOTAL_TIPS_OFFSET, 8),
+        at(
+            "content_standard",
+            CommentAccount::CONTENT_STANDARD_OFFSET,
+            1,
+        ),
+        at("bump", CommentAccount::BUMP_OFFSET, 1),
+        at("prev_comment", CommentAccount::PREV_COMMENT_OFFSET, 33),
+    ],
+};
+
+pub const USER_COUNTER: AccountLayout = AccountLayout {
+    name: "UserCounter",
+    discriminator: UserCounter::DISCRIMINATOR,
+    size: UserCounter::SPACE,
+    fields: &[at("count", 8, 8), at("bump", 16, 1)],
+};
+
+pub const TIP_LEDGER: AccountLayout = AccountLayout {
+    name: "TipLedger",
+    discriminator: TipLedger::DISCRIMINATOR,
+    size: TipLedger::SPACE,
+    fields: &[
+        at("confession", 8, 32),
+        at("sol_total", 40, 16),
+        at("bump", 56, 1),
+    ],
+};
+
+pub const GLOBAL_STATS: AccountLayout = AccountLayout {
+    name: "GlobalStats",
+    discriminator: GlobalStats::DISCRIMINATOR,
+    size: GlobalStats::SPACE,
+    fields: &[
+        at("total_confessions", 8, 8),
+        at("total_likes", 16, 8),
+        at("total_comments", 24, 8),
+        at("next_confession_index", 32, 8),
+        at("bump", 40, 1),
+    ],
+};
+
+pub const SNAPSHOT: AccountLayout = AccountLayout {
+    name: "Snapshot",
+    discriminator: Snapshot::DISCRIMINATOR,
+    size: Snapshot::SPACE,
+    fields: &[
+        at("confession", 8, 32),
+        at("label", 40, 8),
+        at("like_count", 48, 8),
+        at("comment_count", 56, 8),
+        at("tip_events", 64, 4),
+        at("sol_total", 68, 16),
+        at("timestamp", 84, 8),
+        at("slot", 92, 8),
+        at("bump", 100, 1),
+    ],
+};
+
+/// Every program-owned account type.
+pub const ACCOUNTS: &[AccountLayout] = &[
+    CONFESSION_ACCOUNT,
+    COMMENT_ACCOUNT,
+    USER_COUNTER,
+    TIP_LEDGER,
+    GLOBAL_STATS,
+    SNAPSHOT,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{sample_comment, sample_confession};
+    use crate::*;
+
+    fn key(byte: u8) -> Pubkey {
+        Pubkey::new_from_array([byte; 32])
+    }
+
+    /// A field serialized on its own, and whether it is an `Option`.
+    struct Sample(Vec<u8>, bool);
+
+    fn ser<T: AnchorSerialize>(value: T) -> Sample {
+        Sample(value.try_to_vec().unwrap(), false)
+    }
+
+    fn opt(value: Option<Pubkey>) -> Sample {
+        assert!(value.is_some(), "sample options must be Some");
+        Sample(value.try_to_vec().unwrap(), true)
+    }
+
+    /// `fields` holds each field of `account` in declaration order.
+    fn assert_layout<T: AccountSerialize>(layout: &AccountLayout, account: &T, fields: &[Sample]) {
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+
+        assert_eq!(&data[..8], layout.discriminator, "{}", layout.name);
+        assert_eq!(data.len(), layout.size, "{}", layout.name);
+        assert_eq!(layout.fields.len(), fields.len(), "{}", layout.name);
+
+        let mut pos = 8;
+        let mut fixed = true;
+        for (entry, Sample(bytes, is_option)) in layout.fields.iter().zip(fields) {
+            let name = format!("{}.{}", layout.name, entry.name);
+            assert_eq!(entry.len, bytes.len(), "{name} len");
+            if fixed {
+                assert_eq!(entry.offset, Some(pos), "{name} offset");
+            } else {
+                assert_eq!(entry.offset, None, "{name} offset");
+            }
+            assert_eq!(&data[pos..pos + bytes.len()], bytes.as_slice(), "{name}");
+
+            // Anything after an `Option` moves with its tag
+            fixed &= !is_option;
+            pos += bytes.len();
+        }
+        assert_eq!(pos, data.len(), "{}", layout.name);
+    }
+
+    #[test]
+    fn confession_account_layout() {
+        let confession = sample_confession();
+
+        let fields = [
+            ser(confession.author),
+            ser(confession.uri),
+            ser(confession.uri_len),
+            ser(confession.like_count),
+            ser(confession.comment_count),
+            ser(confession.timestamp),
+            ser(confession.slot),
+            ser(confession.status),
+            ser(confession.published_at),
+            ser(confession.tip_events),
+            ser(confession.comments_frozen_until),
+            ser(confession.tip_goal_lamports),
+            ser(confession.goal_reached_at),
+            ser(confession.scheduled_for),
+            ser(confession.rent_payer),
+            ser(confession.coauthors),
+            ser(confession.checkin_interval),
+            ser(confession.release_deadline),
+            ser(confession.original_author),
+            ser(confession.content_warnings),
+            ser(confession.content_standard),
+            ser(confession.last_activity_at),
+            ser(confession.activity_window_start),
+            ser(confession.activity_count_24h),
+            ser(confession.bump),
+            opt(confession.latest_comment),
+            opt(confession.quoted_comment),
+        ];
+        assert_layout(&CONFESSION_ACCOUNT, &confession, &fields);
+    }
+
+    #[test]
+    fn comment_account_layout() {
+        let comment = sample_comment();
+
+        let fields = [
+            ser(comment.confession),
+            ser(comment.commenter),
+            ser(comment.uri),
+            ser(comment.uri_len),
+            ser(comment.timestamp),
+            ser(comment.slot),
+            ser(comment.quote_count),
+            ser(comment.deleted),
+            ser(comment.rent_payer),
+            ser(comment.total_tips),
+            ser(comment.content_standard),
+            ser(comment.bump),
+            opt(comment.prev_comment),
+        ];
+        assert_layout(&COMMENT_ACCOUNT, &comment, &fields);
+    }
+
+    #[test]
+    fn small_account_layouts() {
+        let counter = UserCounter { count: 1, bump: 2 };
+        assert_layout(
+            &USER_COUNTER,
+            &counter,
+            &[ser(counter.count), ser(counter.bump)],
+        );
+
+        let ledger = TipLedger {
+            confession: key(1),
+            sol_total: 2,
+            bump: 3,
+        };
+        assert_layout(
+            &TIP_LEDGER,
+            &ledger,
+            &[
+                ser(ledger.confession),
+                ser(ledger.sol_total),
+                ser(ledger.bump),
+            ],
+        );
+
+        let stats = GlobalStats {
+            total_confessions: 1,
+            total_likes: 2,
+            total_comments: 3,
+            next_confession_index: 5,
+            bump: 4,
+        };
+        assert_layout(
+            &GLOBAL_STATS,
+            &stats,
+            &[
+                ser(stats.total_confessions),
+                ser(stats.total_likes),
+                ser(stats.total_comments),
+                ser(stats.next_confession_index),
+                ser(stats.bump),
+            ],
+        );
+
+        let snapshot = Snapshot {
+            confession: key(1),
+            label: 2,
+            like_count: 3,
+            comment_count: 4,
+            tip_events: 5,
+            sol_total: 6,
+            timestamp: 7,
+            slot: 8,
+            bump: 9,
+        };
+        assert_layout(
+            &SNAPSHOT,
+            &snapshot,
+            &[
+                ser(snapshot.confession),
+                ser(snapshot.label),
+                ser(snapshot.like_count),
+                ser(snapshot.comment_count),
+                ser(snapshot.tip_events),
+                ser(snapshot.sol_total),
+                ser(snapshot.timestamp),
+                ser(snapshot.slot),
+                ser(snapshot.bump),
+            ],
+        );
+    }
+
+    #[test]
+    fn discriminators_are_unique() {
+        for (i, a) in ACCOUNTS.iter().enumerate() {
+            for b in &ACCOUNTS[i + 1..] {
+                assert_ne!(a.discriminator, b.discriminator, "{} / {}", a.name, b.name);
+            }
+        }
+    }
+}
@@ -1,45 +1,370 @@
 // ============================================
 // FILE: programs/whisper/src/lib.rs
 // ============================================
+// `#[program]` expands to IDL instructions that call the deprecated
+// `AccountInfo::realloc` on the pinned solana-program version.
+#![allow(deprecated)]
+// The generated CPI client mirrors `create_confession`'s argument list, which
+// `#[allow]` on the handler doesn't reach.
+#![cfg_attr(feature = "cpi", allow(clippy::too_many_arguments))]
+
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{instruction::Instruction, program::invoke};
+use anchor_lang::system_program;
+
+#[cfg(any(feature = "client", test))]
+pub mod layout;
+#[macro_use]
+pub mod load;
+#[cfg(any(feature = "client", test))]
+pub mod sdk;
+pub mod space;
+pub mod state;
+pub mod validation;
+
+use space::{account_space, Field};
+use state::{assert_commentable, assert_interactable};
+use validation::{
+    validate_content_standard, validate_content_warnings, validate_legacy_account, validate_uri,
+};
 
 declare_id!("DHTV8Z1MNm7C5vNX5mUrR1QdNzipbytaHFimTZbycH9R");
 
+pub const MEMO_PROGRAM_ID: Pubkey = pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+pub const MAX_MEMO_LENGTH: usize = 128;
+pub const DEFAULT_COMMENT_FREEZE_SECONDS: i64 = 24 * 60 * 60;
+pub const MAX_COMMENT_FREEZE_SECONDS: i64 = 7 * 24 * 60 * 60;
+pub const EDIT_WINDOW_SECONDS: i64 = 10 * 60;
+pub const MAX_COAUTHORS: usize = 2;
+/// Length of the rolling window behind `ConfessionAccount::activity_count_24h`.
+pub const ACTIVITY_WINDOW_SECONDS: i64 = 24 * 60 * 60;
+/// Paid from a dormant confession to whoever triggers its release.
+pub const DEADMAN_BOUNTY_LAMPORTS: u64 = 100_000;
+
+/// Bits of `ConfessionAccount::content_warnings`. Warnings stack and can
+/// only be added, never cleared.
+pub mod content_warning {
+    pub const SELF_HARM: u16 = 1 << 0;
+    pub const SUBSTANCE_ABUSE: u16 = 1 << 1;
+    pub const VIOLENCE: u16 = 1 << 2;
+    pub const EATING_DISORDERS: u16 = 1 << 3;
+
+    /// Every defined bit; anything outside it is rejected.
+    pub const ALL: u16 = SELF_HARM | SUBSTANCE_ABUSE | VIOLENCE | EATING_DISORDERS;
+}
+
+/// Versions of the off-chain content format a URI points to, stored as
+/// `content_standard` on confessions and comments. The program only checks
+/// the version is known; the content itself is never read on chain.
+pub mod standards {
+    /// Opaque content with no agreed schema.
+    pub const RAW: u8 = 0;
+    /// whisper-json-v1: a JSON document with `text`, `media` and `tags`.
+    pub const WHISPER_JSON_V1: u8 = 1;
+
+    /// Highest version this build accepts.
+    pub const LATEST: u8 = WHISPER_JSON_V1;
+}
+
+pub const CONFESSION_SEED: &[u8] = b"confession";
+pub const COMMENT_SEED: &[u8] = b"comment";
+pub const STATS_SEED: &[u8] = b"stats";
+pub const TIP_LEDGER_SEED: &[u8] = b"tip_ledger";
+pub const USER_COUNTER_SEED: &[u8] = b"user_counter";
+pub const SNAPSHOT_SEED: &[u8] = b"snapshot";
+
 #[program]
 pub mod whisper {
     use super::*;
 
+    #[allow(clippy::too_many_arguments)]
     pub fn create_confession(
         ctx: Context<CreateConfession>,
         content_uri: String,
-    ) -> Result<()> {
+        quoted_comment: Option<Pubkey>,
+        publish: bool,
+        tip_goal_lamports: Option<u64>,
+        publish_at: Option<i64>,
+        content_warnings: u16,
+        content_standard: u8,
+    ) -> Result<CreatedAccount> {
+        validate_uri(&content_uri, ConfessionAccount::MAX_URI_LENGTH)?;
+        validate_content_warnings(content_warnings)?;
+        validate_content_standard(content_standard)?;
+        require!(
+            quoted_comment == ctx.accounts.quoted_comment.as_ref().map(|c| c.key()),
+            WhisperError::QuotedCommentMismatch
+        );
+        require!(
+            ctx.accounts.quoted_confession.as_ref().map(|c| c.key())
+                == ctx.accounts.quoted_comment.as_ref().map(|c| c.confession),
+            WhisperError::QuotedCommentMismatch
+        );
+        require!(tip_goal_lamports != Some(0), WhisperError::InvalidTipGoal);
         require!(
-            content_uri.len() <= ConfessionAccount::MAX_URI_LENGTH,
-            WhisperError::ContentUriTooLong
+            !(publish && publish_at.is_some()),
+            WhisperError::InvalidPublishSchedule
         );
-        require!(!content_uri.is_empty(), WhisperError::EmptyContentUri);
 
         let confession = &mut ctx.accounts.confession;
         let clock = Clock::get()?;
+        require!(
+            clock.unix_timestamp != 0,
+            WhisperError::InvalidClockTimestamp
+        );
+
+        init_confession(
+            confession,
+            ctx.accounts.author.key(),
+            ctx.accounts.payer.key(),
+            &content_uri,
+            &clock,
+            ctx.bumps.confession,
+        )?;
+        // A `publish_at` that has already passed publishes immediately
+        let scheduled_for = publish_at.filter(|&at| at > clock.unix_timestamp);
+        let publish = publish || (publish_at.is_some() && scheduled_for.is_none());
+        confession.status = if publish {
+            ConfessionStatus::Active
+        } else if scheduled_for.is_some() {
+            ConfessionStatus::Scheduled
+        } else {
+            ConfessionStatus::Draft
+        };
+        confession.published_at = if publish { clock.unix_timestamp } else { 0 };
+        confession.scheduled_for = scheduled_for.unwrap_or(0);
+        confession.tip_goal_lamports = tip_goal_lamports.unwrap_or(0);
+        confession.quoted_comment = quoted_comment;
+        confession.content_warnings = content_warnings;
+        confession.content_standard = content_standard;
+
+        if let Some(quoted) = ctx.accounts.quoted_comment.as_mut() {
+            // Checked for presence together with `quoted_comment` above
+            if let Some(parent) = ctx.accounts.quoted_confession.as_ref() {
+                assert_interactable(parent, &clock)?;
+            }
+            require!(!quoted.deleted, WhisperError::CommentDeleted);
+            quoted.quote_count = quoted
+                .quote_count
+                .checked_add(1)
+                .ok_or(WhisperError::QuoteCountOverflow)?;
+        }
 
-        confession.author = ctx.accounts.author.key();
-        confession.content_uri = content_uri;
-        confession.like_count = 0;
-        confession.comment_count = 0;
-        confession.timestamp = clock.unix_timestamp;
-        confession.bump = ctx.bumps.confession;
+        emit!(ConfessionCreated {
+            confession: confession.key(),
+            author: confession.author,
+            original_author: confession.original_author,
+            content_warnings: confession.content_warnings,
+            content_standard: confession.content_standard,
+            quoted_comment,
+            timestamp: confession.timestamp,
+            slot: confession.slot,
+        });
+
+        let index = if publish {
+            Some(record_publish(confession, &mut ctx.accounts.stats)?)
+        } else {
+            None
+        };
 
         msg!("Confession created: {}", confession.key());
+        Ok(CreatedAccount {
+            address: confession.key(),
+            index,
+            timestamp: confession.timestamp,
+        })
+    }
+
+    /// Creates a confession that stays `Dormant` while the author keeps
+    /// calling `checkin` at least every `checkin_interval` seconds, and can
+    /// be released by anyone once a deadline is missed. The payer funds a
+    /// `DEADMAN_BOUNTY_LAMPORTS` bounty for whoever triggers the release.
+    pub fn create_deadman_confession(
+        ctx: Context<CreateDeadmanConfession>,
+        content_uri: String,
+        checkin_interval: i64,
+        content_warnings: u16,
+        content_standard: u8,
+    ) -> Result<()> {
+        validate_uri(&content_uri, ConfessionAccount::MAX_URI_LENGTH)?;
+        validate_content_warnings(content_warnings)?;
+        validate_content_standard(content_standard)?;
+        require!(checkin_interval > 0, WhisperError::InvalidCheckinInterval);
+
+        let clock = Clock::get()?;
+        require!(
+            clock.unix_timestamp != 0,
+            WhisperError::InvalidClockTimestamp
+        );
+
+        let confession = &mut ctx.accounts.confession;
+        init_confession(
+            confession,
+            ctx.accounts.author.key(),
+            ctx.accounts.payer.key(),
+            &content_uri,
+            &clock,
+            ctx.bumps.confession,
+        )?;
+        confession.status = ConfessionStatus::Dormant;
+        confession.checkin_interval = checkin_interval;
+        confession.content_warnings = content_warnings;
+        confession.content_standard = content_standard;
+        confession.release_deadline = clock
+            .unix_timestamp
+            .checked_add(checkin_interval)
+            .ok_or(WhisperError::InvalidCheckinInterval)?;
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: confession.to_account_info(),
+                },
+            ),
+            DEADMAN_BOUNTY_LAMPORTS,
+        )?;
+
+        emit!(ConfessionCreated {
+            confession: confession.key(),
+            author: confession.author,
+            original_author: confession.original_author,
+            content_warnings: confession.content_warnings,
+            content_standard: confession.content_standard,
+            quoted_comment: None,
+            timestamp: confession.timestamp,
+            slot: confession.slot,
+        });
+
+        msg!(
+            "Dormant confession created, release deadline {}",
+            confession.release_deadline
+        );
+        Ok(())
+    }
+
+    /// Pushes the release deadline `checkin_interval` seconds past now. Must
+    /// land before the current deadline; a missed deadline cannot be undone.
+    pub fn checkin(ctx: Context<Checkin>) -> Result<()> {
+        let confession = &mut ctx.accounts.confession;
+        require!(
+            confession.status == ConfessionStatus::Dormant,
+            WhisperError::ConfessionNotDormant
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now < confession.release_deadline,
+            WhisperError::ReleaseDeadlinePassed
+        );
+        confession.release_deadline = now
+            .checked_add(confession.checkin_interval)
+            .ok_or(WhisperError::InvalidCheckinInterval)?;
+
+        msg!(
+            "Checked in, release deadline {}",
+            confession.release_deadline
+        );
+        Ok(())
+    }
+
+    /// Permissionless release of a dormant confession whose deadline has
+    /// passed. Pays the bounty to `cranker`. As with scheduled confessions,
+    /// the deadline rather than the trigger time becomes `published_at`.
+    pub fn trigger_release(ctx: Context<TriggerRelease>) -> Result<()> {
+        let confession = &mut ctx.accounts.confession;
+        require!(
+            confession.status == ConfessionStatus::Dormant,
+            WhisperError::ConfessionNotDormant
+        );
+        require!(
+            Clock::get()?.unix_timestamp >= confession.release_deadline,
+            WhisperError::ReleaseDeadlineNotReached
+        );
+
+        confession.status = ConfessionStatus::Active;
+        confession.published_at = confession.release_deadline;
+        record_publish(confession, &mut ctx.accounts.stats)?;
+
+        confession.sub_lamports(DEADMAN_BOUNTY_LAMPORTS)?;
+        ctx.accounts.cranker.add_lamports(DEADMAN_BOUNTY_LAMPORTS)?;
+
+        msg!("Dormant confession released: {}", confession.key());
+        Ok(())
+    }
+
+    /// Moves a draft to `Active`. Feeds should order by the `published_at`
+    /// stamped here, not by the draft's creation `timestamp`. A scheduled
+    /// confession can also be published early this way, but a dormant one
+    /// can't: only `trigger_release` pays out its bounty.
+    pub fn publish_confession(ctx: Context<PublishConfession>) -> Result<()> {
+        let confession = &mut ctx.accounts.confession;
+        require!(
+            confession.status != ConfessionStatus::Active,
+            WhisperError::ConfessionAlreadyPublished
+        );
+        require!(
+            confession.status != ConfessionStatus::Dormant,
+            WhisperError::ConfessionDormant
+        );
+
+        let clock = Clock::get()?;
+        confession.status = ConfessionStatus::Active;
+        confession.published_at = clock.unix_timestamp;
+        confession.scheduled_for = 0;
+        record_publish(confession, &mut ctx.accounts.stats)?;
+
+        msg!("Confession published: {}", confession.key());
+        Ok(())
+    }
+
+    /// Permissionless crank that makes a scheduled confession live once its
+    /// `scheduled_for` time has passed. Likes, comments and tips do the same
+    /// on their own, so this is only needed when nobody has interacted yet.
+    pub fn activate_scheduled_confession(ctx: Context<ActivateScheduledConfession>) -> Result<()> {
+        let confession = &mut ctx.accounts.confession;
+        require!(
+            confession.status == ConfessionStatus::Scheduled,
+            WhisperError::ConfessionNotScheduled
+        );
+
+        activate_if_due(confession, &mut ctx.accounts.stats)?;
+        assert_interactable(confession, &Clock::get()?)?;
+
+        msg!("Scheduled confession activated: {}", confession.key());
         Ok(())
     }
 
     pub fn like_confession(ctx: Context<LikeConfession>) -> Result<()> {
         let confession = &mut ctx.accounts.confession;
-        
-        confession.like_count = confession
-            .like_count
-            .checked_add(1)
-            .ok_or(WhisperError::LikeCountOverflow)?;
+        record_like(confession, &mut ctx.accounts.stats)?;
+
+        msg!("Confession liked. Total likes: {}", confession.like_count);
+        Ok(())
+    }
+
+    /// Same as `like_confession`, plus a private note attached to the
+    /// transaction through the SPL Memo program instead of account state.
+    pub fn like_confession_with_memo(
+        ctx: Context<LikeConfessionWithMemo>,
+        memo: String,
+    ) -> Result<()> {
+        require!(!memo.is_empty(), WhisperError::EmptyMemo);
+        require!(memo.len() <= MAX_MEMO_LENGTH, WhisperError::MemoTooLong);
+
+        let confession = &mut ctx.accounts.confession;
+        record_like(confession, &mut ctx.accounts.stats)?;
+
+        let user = ctx.accounts.user.to_account_info();
+        invoke(
+            &Instruction {
+                program_id: MEMO_PROGRAM_ID,
+                accounts: vec![AccountMeta::new_readonly(user.key(), true)],
+                data: memo.into_bytes(),
+            },
+            &[user, ctx.accounts.memo_program.to_account_info()],
+        )?;
 
         msg!("Confession liked. Total likes: {}", confession.like_count);
         Ok(())
@@ -48,35 +373,314 @@ pub mod whisper {
     pub fn comment_confession(
         ctx: Context<CommentConfession>,
         content_uri: String,
-    ) -> Result<()> {
-        require!(
-            content_uri.len() <= CommentAccount::MAX_URI_LENGTH,
-            WhisperError::ContentUriTooLong
-        );
-        require!(!content_uri.is_empty(), WhisperError::EmptyContentUri);
+        content_standard: u8,
+    ) -> Result<CreatedAccount> {
+        validate_uri(&content_uri, CommentAccount::MAX_URI_LENGTH)?;
+        validate_content_standard(content_standard)?;
 
         let confession = &mut ctx.accounts.confession;
+        activate_if_due(confession, &mut ctx.accounts.stats)?;
+
         let comment = &mut ctx.accounts.comment;
         let clock = Clock::get()?;
+        require!(
+            clock.unix_timestamp != 0,
+            WhisperError::InvalidClockTimestamp
+        );
+        assert_commentable(confession, &clock)?;
 
         comment.confession = confession.key();
         comment.commenter = ctx.accounts.commenter.key();
-        comment.content_uri = content_uri;
+        comment.set_content_uri(&content_uri)?;
         comment.timestamp = clock.unix_timestamp;
+        comment.slot = clock.slot;
+        comment.quote_count = 0;
+        comment.deleted = false;
+        comment.rent_payer = ctx.accounts.payer.key();
+        comment.total_tips = 0;
+        comment.content_standard = content_standard;
         comment.bump = ctx.bumps.comment;
+        comment.prev_comment = confession.latest_comment;
+
+        confession.latest_comment = Some(comment.key());
 
+        let index = confession.comment_count;
         confession.comment_count = confession
             .comment_count
             .checked_add(1)
             .ok_or(WhisperError::CommentCountOverflow)?;
+        confession.record_activity(clock.unix_timestamp);
+
+        let stats = &mut ctx.accounts.stats;
+        stats.total_comments = stats
+            .total_comments
+            .checked_add(1)
+            .ok_or(WhisperError::CommentCountOverflow)?;
+
+        emit!(CommentAdded {
+            confession: confession.key(),
+            comment: comment.key(),
+            commenter: comment.commenter,
+            comment_index: index,
+            prev_comment: comment.prev_comment,
+            comment_count: confession.comment_count,
+            content_standard: comment.content_standard,
+            timestamp: comment.timestamp,
+            slot: comment.slot,
+        });
 
         msg!("Comment added to confession: {}", confession.key());
+        Ok(CreatedAccount {
+            address: comment.key(),
+            index: Some(index),
+            timestamp: comment.timestamp,
+        })
+    }
+
+    /// Blanks the comment's URI and marks it deleted. The account stays
+    /// alive so `prev_comment` links through it and quotes of it stay valid.
+    pub fn soft_delete_comment(ctx: Context<SoftDeleteComment>) -> Result<()> {
+        let comment = &mut ctx.accounts.comment;
+        require!(!comment.deleted, WhisperError::CommentDeleted);
+
+        comment.set_content_uri("")?;
+        comment.deleted = true;
+
+        emit_comment_deleted(comment, false, DeletionReason::Author)?;
+
+        msg!("Comment soft-deleted: {}", comment.key());
+        Ok(())
+    }
+
+    /// Closes the comment and refunds its rent to whoever paid it. Only
+    /// allowed while it has no quotes. The chain is spliced around it: the
+    /// confession's `latest_comment` moves back to `prev_comment` when the
+    /// head is purged, otherwise `next_comment` (the one whose
+    /// `prev_comment` is this comment) must be passed and is relinked.
+    /// Comments left by a deleted confession go through
+    /// `close_orphan_comment` instead.
+    pub fn purge_comment(ctx: Context<PurgeComment>) -> Result<()> {
+        let confession = &mut ctx.accounts.confession;
+        let comment = &ctx.accounts.comment;
+        require!(
+            comment.slot >= confession.slot,
+            WhisperError::CommentOrphaned
+        );
+        require!(
+            comment.quote_count == 0,
+            WhisperError::CommentStillReferenced
+        );
+
+        let relinked_comment = if confession.latest_comment == Some(comment.key()) {
+            require!(
+                ctx.accounts.next_comment.is_none(),
+                WhisperError::NextCommentMismatch
+            );
+            confession.latest_comment = comment.prev_comment;
+            None
+        } else {
+            let next = ctx
+                .accounts
+                .next_comment
+                .as_mut()
+                .ok_or(WhisperError::NextCommentMismatch)?;
+            require!(
+                next.confession == confession.key() && next.prev_comment == Some(comment.key()),
+                WhisperError::NextCommentMismatch
+            );
+            next.prev_comment = comment.prev_comment;
+            Some(next.key())
+        };
+
+        confession.comment_count = confession
+            .comment_count
+            .checked_sub(1)
+            .ok_or(WhisperError::CommentCountOverflow)?;
+
+        let stats = &mut ctx.accounts.stats;
+        stats.total_comments = stats
+            .total_comments
+            .checked_sub(1)
+            .ok_or(WhisperError::CommentCountOverflow)?;
+
+        emit!(CommentChainRepaired {
+            confession: confession.key(),
+            removed_comment: comment.key(),
+            relinked_comment,
+            latest_comment: confession.latest_comment,
+            comment_count: confession.comment_count,
+            slot: Clock::get()?.slot,
+        });
+        emit_comment_deleted(comment, true, DeletionReason::Author)?;
+
+        msg!("Comment purged: {}", comment.key());
+        Ok(())
+    }
+
+    /// Closes the confession and refunds its rent to whoever paid it. The
+    /// `ConfessionDeleted` event is emitted first so indexers keep a
+    /// tombstone of the final state.
+    ///
+    /// Comments are left in place and stay readable. They drop out of
+    /// `GlobalStats`, and anyone can later close them with
+    /// `close_orphan_comment`, which refunds their rent payers. A quote of a
+    /// comment is released, letting that comment be purged later. The
+    /// `TipLedger` is closed too, so a recreated confession starts its tip
+    /// totals and goal progress from zero.
+    pub fn delete_confession(ctx: Context<DeleteConfession>) -> Result<()> {
+        let confession = &ctx.accounts.confession;
+        require!(
+            ctx.accounts.quoted_comment.as_ref().map(|c| c.key()) == confession.quoted_comment,
+            WhisperError::QuotedCommentMismatch
+        );
+        if let Some(quoted) = ctx.accounts.quoted_comment.as_mut() {
+            quoted.quote_count = quoted
+                .quote_count
+                .checked_sub(1)
+                .ok_or(WhisperError::QuoteCountOverflow)?;
+        }
+
+        // The ledger only exists once someone has tipped. Its address is
+        // pinned by seeds, so a program-owned account there is the ledger.
+        let ledger = ctx.accounts.tip_ledger.to_account_info();
+        if ledger.owner == &crate::ID {
+            let lamports = ledger.lamports();
+            ledger.sub_lamports(lamports)?;
+            ctx.accounts.rent_payer.add_lamports(lamports)?;
+            ledger.assign(&system_program::ID);
+            ledger.resize(0)?;
+        }
+
+        let stats = &mut ctx.accounts.stats;
+        if confession.status == ConfessionStatus::Active {
+            stats.total_confessions = stats
+                .total_confessions
+                .checked_sub(1)
+                .ok_or(WhisperError::ConfessionCountOverflow)?;
+        }
+        stats.total_likes = stats
+            .total_likes
+            .checked_sub(confession.like_count)
+            .ok_or(WhisperError::LikeCountOverflow)?;
+        stats.total_comments = stats
+            .total_comments
+            .checked_sub(confession.comment_count)
+            .ok_or(WhisperError::CommentCountOverflow)?;
+
+        let clock = Clock::get()?;
+        emit!(ConfessionDeleted {
+            confession: confession.key(),
+            author: confession.author,
+            like_count: confession.like_count,
+            comment_count: confession.comment_count,
+            reason: DeletionReason::Author,
+            timestamp: clock.unix_timestamp,
+            slot: clock.slot,
+        });
+
+        msg!("Confession deleted: {}", confession.key());
+        Ok(())
+    }
+
+    /// Permissionless. Closes a comment whose confession was deleted and
+    /// refunds its rent payer. The confession address may since hold a new
+    /// confession from the same author; a comment older than that
+    /// confession's creation slot belongs to the deleted one. Closing it
+    /// also frees the commenter's address for a comment on the new one.
+    /// Quoted comments stay until the quoting confession is deleted.
+    pub fn close_orphan_comment(ctx: Context<CloseOrphanComment>) -> Result<()> {
+        let comment = &ctx.accounts.comment;
+        let confession = &ctx.accounts.confession;
+        let orphaned = if confession.owner == &crate::ID {
+            let current =
+                ConfessionAccount::try_deserialize(&mut &confession.try_borrow_data()?[..])?;
+            current.slot > comment.slot
+        } else {
+            true
+        };
+        require!(orphaned, WhisperError::CommentNotOrphaned);
+        require!(
+            comment.quote_count == 0,
+            WhisperError::CommentStillReferenced
+        );
+
+        emit_comment_deleted(comment, true, DeletionReason::Orphaned)?;
+
+        msg!("Orphaned comment closed: {}", comment.key());
+        Ok(())
+    }
+
+    /// Closes a confession or comment left over from the `String`-URI
+    /// layout, which the current program can no longer deserialize, and
+    /// refunds its rent to its author or commenter, who always paid it. See
+    /// `validate_legacy_account` for what counts as legacy. The legacy build
+    /// kept no `GlobalStats`, so no totals change.
+    pub fn close_legacy_account(ctx: Context<CloseLegacyAccount>) -> Result<()> {
+        let legacy = ctx.accounts.legacy.to_account_info();
+        require!(legacy.owner == &crate::ID, WhisperError::NotLegacyAccount);
+
+        validate_legacy_account(&legacy.try_borrow_data()?, ctx.accounts.owner.key)?;
+
+        let lamports = legacy.lamports();
+        legacy.sub_lamports(lamports)?;
+        ctx.accounts.owner.add_lamports(lamports)?;
+        legacy.assign(&system_program::ID);
+        legacy.resize(0)?;
+
+        msg!("Legacy account closed: {}", legacy.key());
+        Ok(())
+    }
+
+    /// Temporarily blocks new comments for `duration` seconds (24h when
+    /// omitted, at most 7 days). Likes are unaffected. Re-freezing replaces
+    /// the previous deadline.
+    pub fn freeze_comments(ctx: Context<FreezeComments>, duration: Option<i64>) -> Result<()> {
+        let duration = duration.unwrap_or(DEFAULT_COMMENT_FREEZE_SECONDS);
+        require!(duration > 0, WhisperError::InvalidFreezeDuration);
+        require!(
+            duration <= MAX_COMMENT_FREEZE_SECONDS,
+            WhisperError::FreezeDurationTooLong
+        );
+
+        let confession = &mut ctx.accounts.confession;
+        confession.comments_frozen_until = Clock::get()?
+            .unix_timestamp
+            .checked_add(duration)
+            .ok_or(WhisperError::InvalidFreezeDuration)?;
+
+        msg!("Comments frozen until {}", confession.comments_frozen_until);
+        Ok(())
+    }
+
+    pub fn thaw_comments(ctx: Context<ThawComments>) -> Result<()> {
+        ctx.accounts.confession.comments_frozen_until = 0;
+
+        msg!("Comments thawed: {}", ctx.accounts.confession.key());
         Ok(())
     }
 
-    /// [FOSS ISSUE] Beginner: Add logic to close the confession account and reclaim rent
-    pub fn delete_confession(_ctx: Context<DeleteConfession>) -> Result<()> {
-        // TODO: Use Anchor's `close` constraint or manual account closing logic
+    /// Adds `content_warning` bits on top of the existing ones. Bits already
+    /// set stay set, so a warning can never be removed this way, and there
+    /// is no instruction that clears one.
+    ///
+    /// The request called for moderators to add warnings, but the program
+    /// has no moderator role, so this is open to the author and co-authors
+    /// only. Because bits only stack, the author still cannot clear a
+    /// warning a co-author added.
+    pub fn add_content_warning(ctx: Context<AddContentWarning>, warnings: u16) -> Result<()> {
+        require!(warnings != 0, WhisperError::InvalidContentWarning);
+        validate_content_warnings(warnings)?;
+
+        let confession = &mut ctx.accounts.confession;
+        confession.content_warnings |= warnings;
+
+        emit!(ContentWarningAdded {
+            confession: confession.key(),
+            added: warnings,
+            content_warnings: confession.content_warnings,
+            by: ctx.accounts.collaborator.key(),
+            slot: Clock::get()?.slot,
+        });
         Ok(())
     }
 
@@ -86,19 +690,233 @@ pub mod whisper {
         Ok(())
     }
 
-    /// [FOSS ISSUE] Medium: Add logic to edit the confession content
-    /// Restricted to a 10-minute window from creation time.
-    pub fn edit_confession(
-        _ctx: Context<EditConfession>,
-        _new_content_uri: String,
+    /// Replaces the content URI. Open to the author and co-authors, and
+    /// restricted to a 10-minute window from creation time.
+    pub fn edit_confession(ctx: Context<EditConfession>, new_content_uri: String) -> Result<()> {
+        validate_uri(&new_content_uri, ConfessionAccount::MAX_URI_LENGTH)?;
+
+        let confession = &mut ctx.accounts.confession;
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now <= confession.timestamp.saturating_add(EDIT_WINDOW_SECONDS),
+            WhisperError::EditWindowClosed
+        );
+
+        confession.set_content_uri(&new_content_uri)?;
+
+        msg!("Confession edited: {}", confession.key());
+        Ok(())
+    }
+
+    /// Grants `coauthor` edit and comment-freeze rights. Deleting stays
+    /// with the primary author.
+    pub fn add_coauthor(ctx: Context<AddCoauthor>, coauthor: Pubkey) -> Result<()> {
+        ctx.accounts.confession.add_coauthor(coauthor)?;
+
+        msg!("Co-author added: {}", coauthor);
+        Ok(())
+    }
+
+    pub fn remove_coauthor(ctx: Context<RemoveCoauthor>, coauthor: Pubkey) -> Result<()> {
+        ctx.accounts.confession.remove_coauthor(coauthor)?;
+
+        msg!("Co-author removed: {}", coauthor);
+        Ok(())
+    }
+
+    /// Hands the confession to `claimer`. Both the current author (e.g. the
+    /// relayer that posted on the user's behalf) and the claimer sign.
+    pub fn claim_confession(ctx: Context<ClaimConfession>) -> Result<()> {
+        let confession = &mut ctx.accounts.confession;
+        let claimer = ctx.accounts.claimer.key();
+        confession.hand_over(claimer)?;
+
+        emit!(ConfessionClaimed {
+            confession: confession.key(),
+            previous_author: ctx.accounts.author.key(),
+            new_author: claimer,
+            slot: Clock::get()?.slot,
+        });
+
+        msg!("Confession claimed by {}", claimer);
+        Ok(())
+    }
+
+    /// `claim_confession` for every writable confession passed in
+    /// `remaining_accounts`, all currently authored by `author`.
+    pub fn claim_confessions<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimConfessions<'info>>,
     ) -> Result<()> {
-        // TODO: Check clock.unix_timestamp against account creation timestamp
+        let author = ctx.accounts.author.key();
+        let claimer = ctx.accounts.claimer.key();
+        let slot = Clock::get()?.slot;
+
+        for info in ctx.remaining_accounts {
+            require!(info.is_writable, ErrorCode::AccountNotMutable);
+            let mut confession = Account::<ConfessionAccount>::try_from(info)?;
+            require_keys_eq!(confession.author, author, WhisperError::NotConfessionAuthor);
+            confession.hand_over(claimer)?;
+            confession.exit(&crate::ID)?;
+
+            emit!(ConfessionClaimed {
+                confession: info.key(),
+                previous_author: author,
+                new_author: claimer,
+                slot,
+            });
+        }
+
+        msg!(
+            "{} confessions claimed by {}",
+            ctx.remaining_accounts.len(),
+            claimer
+        );
+        Ok(())
+    }
+
+    /// Sends `amount` lamports straight to the author. Running totals live
+    /// in the confession's `TipLedger` (created on the first tip) so a
+    /// lifetime of tips cannot overflow a `u64`.
+    pub fn tip_author(ctx: Context<TipAuthor>, amount: u64) -> Result<()> {
+        require!(amount > 0, WhisperError::InvalidTipAmount);
+
+        let confession = &mut ctx.accounts.confession;
+        activate_if_due(confession, &mut ctx.accounts.stats)?;
+        assert_interactable(confession, &Clock::get()?)?;
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.tipper.to_account_info(),
+                    to: ctx.accounts.author.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let ledger = &mut ctx.accounts.tip_ledger;
+        if ledger.confession == Pubkey::default() {
+            ledger.confession = confession.key();
+            ledger.bump = ctx.bumps.tip_ledger;
+        }
+        let previous_total = ledger.sol_total;
+        ledger.sol_total = add_tip(previous_total, amount)?;
+
+        confession.tip_events = confession
+            .tip_events
+            .checked_add(1)
+            .ok_or(WhisperError::TipLedgerOverflow)?;
+        confession.record_activity(Clock::get()?.unix_timestamp);
+
+        let goal = confession.tip_goal_lamports;
+        let before = goal_quarters(previous_total, goal);
+        let after = goal_quarters(ledger.sol_total, goal);
+        if after > before {
+            let clock = Clock::get()?;
+            for quarter in before + 1..=after {
+                emit!(TipGoalProgress {
+                    confession: confession.key(),
+                    percent: quarter * 25,
+                    total_tips_lamports: ledger.sol_total,
+                    tip_goal_lamports: goal,
+                    timestamp: clock.unix_timestamp,
+                    slot: clock.slot,
+                });
+            }
+            if after == 4 && confession.goal_reached_at == 0 {
+                confession.goal_reached_at = clock.unix_timestamp;
+            }
+        }
+
+        msg!(
+            "Tipped {} lamports. Total tips: {}",
+            amount,
+            ledger.sol_total
+        );
+        Ok(())
+    }
+
+    /// Sends `amount` lamports straight to the commenter and adds it to the
+    /// comment's `total_tips`. Counts as activity on the comment's
+    /// confession.
+    pub fn tip_comment(ctx: Context<TipComment>, amount: u64) -> Result<()> {
+        require!(amount > 0, WhisperError::InvalidTipAmount);
+
+        let comment = &mut ctx.accounts.comment;
+        let confession = &mut ctx.accounts.confession;
+        require!(!comment.deleted, WhisperError::CommentDeleted);
+        require!(
+            comment.slot >= confession.slot,
+            WhisperError::CommentOrphaned
+        );
+        require!(
+            ctx.accounts.tipper.key() != comment.commenter,
+            WhisperError::CannotTipSelf
+        );
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.tipper.to_account_info(),
+                    to: ctx.accounts.commenter.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        comment.total_tips = comment
+            .total_tips
+            .checked_add(amount)
+            .ok_or(WhisperError::CommentTipOverflow)?;
+        confession.record_activity(Clock::get()?.unix_timestamp);
+
+        emit!(CommentTipped {
+            comment: comment.key(),
+            confession: comment.confession,
+            commenter: comment.commenter,
+            tipper: ctx.accounts.tipper.key(),
+            amount,
+            total_tips: comment.total_tips,
+            slot: Clock::get()?.slot,
+        });
+
+        msg!(
+            "Tipped comment {} lamports. Total tips: {}",
+            amount,
+            comment.total_tips
+        );
         Ok(())
     }
 
-    /// [FOSS ISSUE] Medium: Add logic to transfer SOL to the author
-    pub fn tip_author(_ctx: Context<TipAuthor>, _amount: u64) -> Result<()> {
-        // TODO: Transfer SOL from tipper to author via system_program CPI
+    /// Permissionless. Copies the confession's engagement counters into a
+    /// new `Snapshot` at `[SNAPSHOT_SEED, confession, label]`. The account is
+    /// created with `init`, so each label can be used only once and the
+    /// snapshot can't be taken again later with better numbers.
+    pub fn snapshot_confession(ctx: Context<SnapshotConfession>, label: u64) -> Result<()> {
+        let confession = &ctx.accounts.confession;
+        // The ledger only exists once someone has tipped
+        let ledger = &ctx.accounts.tip_ledger;
+        let sol_total = if ledger.owner == &crate::ID {
+            TipLedger::try_deserialize(&mut &ledger.try_borrow_data()?[..])?.sol_total
+        } else {
+            0
+        };
+        let clock = Clock::get()?;
+
+        let snapshot = &mut ctx.accounts.snapshot;
+        snapshot.confession = confession.key();
+        snapshot.label = label;
+        snapshot.like_count = confession.like_count;
+        snapshot.comment_count = confession.comment_count;
+        snapshot.tip_events = confession.tip_events;
+        snapshot.sol_total = sol_total;
+        snapshot.timestamp = clock.unix_timestamp;
+        snapshot.slot = clock.slot;
+        snapshot.bump = ctx.bumps.snapshot;
+
+        msg!("Snapshot {} taken: {}", label, confession.key());
         Ok(())
     }
 
@@ -109,84 +927,688 @@ pub mod whisper {
         user_counter.bump = ctx.bumps.user_counter;
         Ok(())
     }
+
+    /// One-time setup of the site-wide stats PDA. Must run before the first
+    /// confession, like, or comment since those instructions require it.
+    pub fn initialize_global_stats(ctx: Context<InitializeGlobalStats>) -> Result<()> {
+        let stats = &mut ctx.accounts.stats;
+        stats.total_confessions = 0;
+        stats.total_likes = 0;
+        stats.total_comments = 0;
+        stats.next_confession_index = 0;
+        stats.bump = ctx.bumps.stats;
+        Ok(())
+    }
 }
 
-// ============================================
-// ACCOUNT STRUCTURES
-// ============================================
+fn record_like(confession: &mut Account<ConfessionAccount>, stats: &mut GlobalStats) -> Result<()> {
+    activate_if_due(confession, stats)?;
+    assert_interactable(confession, &Clock::get()?)?;
 
-#[account]
-pub struct ConfessionAccount {
-    pub author: Pubkey,
-    pub content_uri: String,
-    pub like_count: u64,
-    pub comment_count: u64,
-    pub timestamp: i64,
-    pub bump: u8,
+    confession.like_count = confession
+        .like_count
+        .checked_add(1)
+        .ok_or(WhisperError::LikeCountOverflow)?;
+    confession.record_activity(Clock::get()?.unix_timestamp);
+
+    stats.total_likes = stats
+        .total_likes
+        .checked_add(1)
+        .ok_or(WhisperError::LikeCountOverflow)?;
+    Ok(())
 }
 
-impl ConfessionAccount {
-    pub const MAX_URI_LENGTH: usize = 200;
-    pub const SPACE: usize = 8 + 32 + 4 + Self::MAX_URI_LENGTH + 8 + 8 + 8 + 1;
+/// Tombstone for indexers. `closed` tells a purge, which frees the address,
+/// from a soft delete, which leaves the blanked account in place.
+fn emit_comment_deleted(
+    comment: &Account<CommentAccount>,
+    closed: bool,
+    reason: DeletionReason,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    emit!(CommentDeleted {
+        comment: comment.key(),
+        confession: comment.confession,
+        commenter: comment.commenter,
+        quote_count: comment.quote_count,
+        total_tips: comment.total_tips,
+        closed,
+        reason,
+        timestamp: clock.unix_timestamp,
+        slot: clock.slot,
+    });
+    Ok(())
 }
 
-#[account]
-pub struct CommentAccount {
-    pub confession: Pubkey,
-    pub commenter: Pubkey,
-    pub content_uri: String,
-    pub timestamp: i64,
-    pub bump: u8,
+fn add_tip(total: u128, amount: u64) -> Result<u128> {
+    total
+        .checked_add(amount as u128)
+        .ok_or(error!(WhisperError::TipLedgerOverflow))
 }
 
-impl CommentAccount {
-    pub const MAX_URI_LENGTH: usize = 200;
-    pub const SPACE: usize = 8 + 32 + 32 + 4 + Self::MAX_URI_LENGTH + 8 + 1;
+/// How many 25% steps of `goal` the `total` has reached, capped at 4.
+/// Always 0 when there is no goal.
+fn goal_quarters(total: u128, goal: u64) -> u8 {
+    if goal == 0 {
+        return 0;
+    }
+    // `total` fits in u128 well below u128::MAX / 4 in practice, but
+    // saturate rather than trust that
+    (total.saturating_mul(4) / goal as u128).min(4) as u8
 }
 
-#[account]
-pub struct UserCounter {
-    pub count: u64,
-    pub bump: u8,
+/// Drafts are left out of `total_confessions` until they are published, so
+/// the global tally only ever counts confessions visible in feeds. Returns
+/// the confession's global index, taken from `next_confession_index`.
+fn record_publish(confession: &Account<ConfessionAccount>, stats: &mut GlobalStats) -> Result<u64> {
+    stats.total_confessions = stats
+        .total_confessions
+        .checked_add(1)
+        .ok_or(WhisperError::ConfessionCountOverflow)?;
+    let index = stats.next_confession_index;
+    stats.next_confession_index = index
+        .checked_add(1)
+        .ok_or(WhisperError::ConfessionCountOverflow)?;
+
+    emit!(ConfessionPublished {
+        confession: confession.key(),
+        author: confession.author,
+        index,
+        published_at: confession.published_at,
+        slot: Clock::get()?.slot,
+    });
+    Ok(index)
 }
 
-impl UserCounter {
-    pub const SPACE: usize = 8 + 8 + 1;
+/// Resets every field of a freshly created confession to a `Draft` with no
+/// comments, tips, schedule or co-authors. Callers then set whatever differs.
+fn init_confession(
+    confession: &mut ConfessionAccount,
+    author: Pubkey,
+    rent_payer: Pubkey,
+    content_uri: &str,
+    clock: &Clock,
+    bump: u8,
+) -> Result<()> {
+    confession.author = author;
+    confession.set_content_uri(content_uri)?;
+    confession.like_count = 0;
+    confession.comment_count = 0;
+    confession.timestamp = clock.unix_timestamp;
+    confession.slot = clock.slot;
+    confession.status = ConfessionStatus::Draft;
+    confession.published_at = 0;
+    confession.tip_events = 0;
+    confession.comments_frozen_until = 0;
+    confession.tip_goal_lamports = 0;
+    confession.goal_reached_at = 0;
+    confession.scheduled_for = 0;
+    confession.rent_payer = rent_payer;
+    confession.coauthors = [Pubkey::default(); MAX_COAUTHORS];
+    confession.checkin_interval = 0;
+    confession.release_deadline = 0;
+    confession.original_author = author;
+    confession.content_warnings = 0;
+    confession.content_standard = standards::RAW;
+    confession.last_activity_at = 0;
+    confession.activity_window_start = 0;
+    confession.activity_count_24h = 0;
+    confession.bump = bump;
+    confession.latest_comment = None;
+    confession.quoted_comment = None;
+    Ok(())
+}
+
+/// Flips a `Scheduled` confession to `Active` once its time has come. The
+/// scheduled time, not the moment of activation, becomes `published_at` so
+/// feed order does not depend on when someone happened to interact.
+fn activate_if_due(
+    confession: &mut Account<ConfessionAccount>,
+    stats: &mut GlobalStats,
+) -> Result<()> {
+    if confession.status != ConfessionStatus::Scheduled
+        || Clock::get()?.unix_timestamp < confession.scheduled_for
+    {
+        return Ok(());
+    }
+
+    confession.status = ConfessionStatus::Active;
+    confession.published_at = confession.scheduled_for;
+    confession.scheduled_for = 0;
+    record_publish(confession, stats)?;
+    Ok(())
 }
 
 // ============================================
-// CONTEXT STRUCTURES
+// ACCOUNT STRUCTURES
 // ============================================
 
-#[derive(Accounts)]
+// URIs are stored as a fixed `[u8; MAX_URI_LENGTH]` buffer plus a `uri_len`
+// prefix instead of a Borsh `String`, so every field sits at a fixed byte
+// offset (discriminator included) that clients can use in memcmp filters.
+//
+// Migration: accounts created before this layout stored `content_uri` as a
+// `String` and cannot be deserialized by the current program. There is no
+// account versioning yet. Loading one fails with `LegacyAccountLayout` (see
+// `load`); its author or commenter can reclaim the rent with
+// `close_legacy_account` and post again. Nothing is carried over;
+// likes and comment links on a legacy confession are lost.
+//
+// `Option<Pubkey>` fields serialize to 1 or 33 bytes, so they are kept after
+// `bump` where their variable width cannot shift any fixed offset.
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ConfessionStatus {
+    Active,
+    Draft,
+    /// Goes live at `scheduled_for`; until then it behaves like a draft.
+    Scheduled,
+    /// Dead-man's-switch confession, released once `release_deadline`
+    /// passes without a check-in.
+    Dormant,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ConfessionAccount {
+    /// Current controller; every authorization check uses this key.
+    pub author: Pubkey,
+    pub uri: [u8; ConfessionAccount::MAX_URI_LENGTH],
+    pub uri_len: u16,
+    pub like_count: u64,
+    pub comment_count: u64,
+    pub timestamp: i64,
+    pub slot: u64,
+    pub status: ConfessionStatus,
+    /// Zero while the confession is a draft.
+    pub published_at: i64,
+    /// Number of tips received; lamport totals live in `TipLedger`.
+    pub tip_events: u32,
+    /// New comments are rejected until this unix timestamp; zero when thawed.
+    pub comments_frozen_until: i64,
+    /// Fundraiser target in lamports, checked against `TipLedger::sol_total`;
+    /// zero when the confession has no goal.
+    pub tip_goal_lamports: u64,
+    /// When tips first reached `tip_goal_lamports`; zero until then.
+    pub goal_reached_at: i64,
+    /// Publish time of a `Scheduled` confession; zero otherwise.
+    pub scheduled_for: i64,
+    /// Who funded the account's rent and gets it back on delete. Usually
+    /// the author, or a sponsor paying on their behalf.
+    pub rent_payer: Pubkey,
+    /// Co-authors allowed to edit and freeze comments. `Pubkey::default()`
+    /// marks an empty slot.
+    pub coauthors: [Pubkey; MAX_COAUTHORS],
+    /// Seconds each `checkin` buys a `Dormant` confession; zero otherwise.
+    pub checkin_interval: i64,
+    /// When a `Dormant` confession becomes releasable; zero otherwise.
+    pub release_deadline: i64,
+    /// Creator, set once and never changed. The confession's address is
+    /// derived from this key, not from `author`, which may change hands.
+    pub original_author: Pubkey,
+    /// `content_warning` bits; only ever gain bits after creation.
+    pub content_warnings: u16,
+    /// `standards` version of the content behind the URI.
+    pub content_standard: u8,
+    /// Time of the latest like, comment or tip; zero before the first.
+    pub last_activity_at: i64,
+    /// Start of the current `ACTIVITY_WINDOW_SECONDS` window.
+    pub activity_window_start: i64,
+    /// Interactions since `activity_window_start`. Reset lazily by the
+    /// first interaction after the window expires, so a stale value can
+    /// linger on idle confessions; check `activity_window_start` first.
+    pub activity_count_24h: u32,
+    pub bump: u8,
+    /// Head of the comment chain, newest first. See `CommentAccount::prev_comment`.
+    pub latest_comment: Option<Pubkey>,
+    /// Comment this confession was elevated from, if any.
+    pub quoted_comment: Option<Pubkey>,
+}
+
+checked_account!(
+    ConfessionAccount,
+    discriminator = [113, 124, 26, 51, 214, 56, 112, 118],
+    legacy_space = ConfessionAccount::LEGACY_SPACE
+);
+
+impl ConfessionAccount {
+    pub const MAX_URI_LENGTH: usize = 200;
+    /// Size of accounts from the `String`-URI build, which loading reports
+    /// as `LegacyAccountLayout`.
+    pub const LEGACY_SPACE: usize = 8 + 32 + 4 + Self::MAX_URI_LENGTH + 8 + 8 + 8 + 1;
+    pub const SPACE: usize = account_space(&[
+        Field::Pubkey,                      // author
+        Field::Bytes(Self::MAX_URI_LENGTH), // uri
+        Field::U16,                         // uri_len
+        Field::U64,                         // like_count
+        Field::U64,                         // comment_count
+        Field::I64,                         // timestamp
+        Field::U64,                         // slot
+        Field::Enum,                        // status
+        Field::I64,                         // published_at
+        Field::U32,                         // tip_events
+        Field::I64,                         // comments_frozen_until
+        Field::U64,                         // tip_goal_lamports
+        Field::I64,                         // goal_reached_at
+        Field::I64,                         // scheduled_for
+        Field::Pubkey,                      // rent_payer
+        Field::Pubkeys(MAX_COAUTHORS),      // coauthors
+        Field::I64,                         // checkin_interval
+        Field::I64,                         // release_deadline
+        Field::Pubkey,                      // original_author
+        Field::U16,                         // content_warnings
+        Field::U8,                          // content_standard
+        Field::I64,                         // last_activity_at
+        Field::I64,                         // activity_window_start
+        Field::U32,                         // activity_count_24h
+        Field::U8,                          // bump
+        Field::OptionPubkey,                // latest_comment
+        Field::OptionPubkey,                // quoted_comment
+    ]);
+
+    pub const AUTHOR_OFFSET: usize = 8;
+    pub const URI_OFFSET: usize = 40;
+    pub const URI_LEN_OFFSET: usize = 240;
+    pub const LIKE_COUNT_OFFSET: usize = 242;
+    pub const COMMENT_COUNT_OFFSET: usize = 250;
+    pub const TIMESTAMP_OFFSET: usize = 258;
+    pub const SLOT_OFFSET: usize = 266;
+    pub const STATUS_OFFSET: usize = 274;
+    pub const PUBLISHED_AT_OFFSET: usize = 275;
+    pub const TIP_EVENTS_OFFSET: usize = 283;
+    pub const COMMENTS_FROZEN_UNTIL_OFFSET: usize = 287;
+    pub const TIP_GOAL_LAMPORTS_OFFSET: usize = 295;
+    pub const GOAL_REACHED_AT_OFFSET: usize = 303;
+    pub const SCHEDULED_FOR_OFFSET: usize = 311;
+    pub const RENT_PAYER_OFFSET: usize = 319;
+    pub const COAUTHORS_OFFSET: usize = 351;
+    pub const CHECKIN_INTERVAL_OFFSET: usize = 415;
+    pub const RELEASE_DEADLINE_OFFSET: usize = 423;
+    pub const ORIGINAL_AUTHOR_OFFSET: usize = 431;
+    pub const CONTENT_WARNINGS_OFFSET: usize = 463;
+    pub const CONTENT_STANDARD_OFFSET: usize = 465;
+    pub const LAST_ACTIVITY_AT_OFFSET: usize = 466;
+    pub const ACTIVITY_WINDOW_START_OFFSET: usize = 474;
+    pub const ACTIVITY_COUNT_24H_OFFSET: usize = 482;
+    pub const BUMP_OFFSET: usize = 486;
+    pub const LATEST_COMMENT_OFFSET: usize = 487;
+
+    pub fn content_uri(&self) -> Result<&str> {
+        read_uri(&self.uri, self.uri_len)
+    }
+
+    pub fn set_content_uri(&mut self, content_uri: &str) -> Result<()> {
+        self.uri_len = write_uri(&mut self.uri, content_uri)?;
+        Ok(())
+    }
+
+    /// The author or one of the co-authors.
+    pub fn is_collaborator(&self, key: &Pubkey) -> bool {
+        *key == self.author || (*key != Pubkey::default() && self.coauthors.contains(key))
+    }
+
+    fn add_coauthor(&mut self, coauthor: Pubkey) -> Result<()> {
+        require!(
+            coauthor != Pubkey::default() && !self.is_collaborator(&coauthor),
+            WhisperError::InvalidCoauthor
+        );
+        let slot = self
+            .coauthors
+            .iter_mut()
+            .find(|slot| **slot == Pubkey::default())
+            .ok_or(WhisperError::CoauthorLimitReached)?;
+        *slot = coauthor;
+        Ok(())
+    }
+
+    fn remove_coauthor(&mut self, coauthor: Pubkey) -> Result<()> {
+        let slot = self
+            .coauthors
+            .iter_mut()
+            .find(|slot| **slot == coauthor && coauthor != Pubkey::default())
+            .ok_or(WhisperError::CoauthorNotFound)?;
+        *slot = Pubkey::default();
+        Ok(())
+    }
+
+    /// Counts one interaction at `now`, first starting a fresh window if the
+    /// current one has run out (or never started).
+    fn record_activity(&mut self, now: i64) {
+        if self.activity_window_start == 0
+            || now.saturating_sub(self.activity_window_start) >= ACTIVITY_WINDOW_SECONDS
+        {
+            self.activity_window_start = now;
+            self.activity_count_24h = 0;
+        }
+        self.activity_count_24h = self.activity_count_24h.saturating_add(1);
+        self.last_activity_at = now;
+    }
+
+    /// Makes `new_author` the author. If they were a co-author, their slot
+    /// is freed. `original_author`, and with it the address, stays put.
+    fn hand_over(&mut self, new_author: Pubkey) -> Result<()> {
+        require!(
+            new_author != Pubkey::default() && new_author != self.author,
+            WhisperError::InvalidClaimer
+        );
+        if let Some(slot) = self.coauthors.iter_mut().find(|slot| **slot == new_author) {
+            *slot = Pubkey::default();
+        }
+        self.author = new_author;
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CommentAccount {
+    pub confession: Pubkey,
+    pub commenter: Pubkey,
+    pub uri: [u8; CommentAccount::MAX_URI_LENGTH],
+    pub uri_len: u16,
+    pub timestamp: i64,
+    pub slot: u64,
+    pub quote_count: u32,
+    /// Set by `soft_delete_comment`, which also blanks the URI.
+    pub deleted: bool,
+    /// Who funded the account's rent and gets it back on purge. Usually
+    /// the commenter, or a sponsor paying on their behalf.
+    pub rent_payer: Pubkey,
+    /// Lifetime lamports tipped to the commenter via `tip_comment`.
+    pub total_tips: u64,
+    /// `standards` version of the content behind the URI.
+    pub content_standard: u8,
+    pub bump: u8,
+    /// The comment that was the confession's newest before this one, so
+    /// clients can page backwards from `latest_comment` with plain account
+    /// fetches. Deleting tombstones the account instead of closing it, and
+    /// `purge_comment` splices the chain around the comment it closes, so
+    /// every link stays valid.
+    pub prev_comment: Option<Pubkey>,
+}
+
+checked_account!(
+    CommentAccount,
+    discriminator = [42, 146, 173, 246, 2, 22, 223, 91],
+    legacy_space = CommentAccount::LEGACY_SPACE
+);
+
+impl CommentAccount {
+    pub const MAX_URI_LENGTH: usize = 200;
+    /// Size of accounts from the `String`-URI build, which loading reports
+    /// as `LegacyAccountLayout`.
+    pub const LEGACY_SPACE: usize = 8 + 32 + 32 + 4 + Self::MAX_URI_LENGTH + 8 + 1;
+    pub const SPACE: usize = account_space(&[
+        Field::Pubkey,                      // confession
+        Field::Pubkey,                      // commenter
+        Field::Bytes(Self::MAX_URI_LENGTH), // uri
+        Field::U16,                         // uri_len
+        Field::I64,                         // timestamp
+        Field::U64,                         // slot
+        Field::U32,                         // quote_count
+        Field::Bool,                        // deleted
+        Field::Pubkey,                      // rent_payer
+        Field::U64,                         // total_tips
+        Field::U8,                          // content_standard
+        Field::U8,                          // bump
+        Field::OptionPubkey,                // prev_comment
+    ]);
+
+    pub const CONFESSION_OFFSET: usize = 8;
+    pub const COMMENTER_OFFSET: usize = 40;
+    pub const URI_OFFSET: usize = 72;
+    pub const URI_LEN_OFFSET: usize = 272;
+    pub const TIMESTAMP_OFFSET: usize = 274;
+    pub const SLOT_OFFSET: usize = 282;
+    pub const QUOTE_COUNT_OFFSET: usize = 290;
+    pub const DELETED_OFFSET: usize = 294;
+    pub const RENT_PAYER_OFFSET: usize = 295;
+    pub const TOTAL_TIPS_OFFSET: usize = 327;
+    pub const CONTENT_STANDARD_OFFSET: usize = 335;
+    pub const BUMP_OFFSET: usize = 336;
+    pub const PREV_COMMENT_OFFSET: usize = 337;
+
+    pub fn content_uri(&self) -> Result<&str> {
+        read_uri(&self.uri, self.uri_len)
+    }
+
+    pub fn set_content_uri(&mut self, content_uri: &str) -> Result<()> {
+        self.uri_len = write_uri(&mut self.uri, content_uri)?;
+        Ok(())
+    }
+}
+
+fn read_uri(buf: &[u8], uri_len: u16) -> Result<&str> {
+    let bytes = buf
+        .get(..uri_len as usize)
+        .ok_or(WhisperError::InvalidUriLength)?;
+    std::str::from_utf8(bytes).map_err(|_| error!(WhisperError::InvalidUriEncoding))
+}
+
+/// Zero-pads `buf` after the copied bytes so stale data from a longer
+/// previous URI never leaks past `uri_len`.
+fn write_uri(buf: &mut [u8], content_uri: &str) -> Result<u16> {
+    let bytes = content_uri.as_bytes();
+    require!(bytes.len() <= buf.len(), WhisperError::ContentUriTooLong);
+
+    buf[..bytes.len()].copy_from_slice(bytes);
+    buf[bytes.len()..].fill(0);
+    Ok(bytes.len() as u16)
+}
+
+#[account]
+pub struct UserCounter {
+    pub count: u64,
+    pub bump: u8,
+}
+
+impl UserCounter {
+    pub const SPACE: usize = account_space(&[
+        Field::U64, // count
+        Field::U8,  // bump
+    ]);
+}
+
+/// Lifetime SOL tips for one confession, at `[b"tip_ledger", confession]`.
+/// Closed together with the confession. SPL tips would get per-mint ledgers
+/// at `[b"tip_ledger", confession, mint]`, but the program has no token
+/// support yet, so only SOL is tracked.
+#[account]
+pub struct TipLedger {
+    pub confession: Pubkey,
+    pub sol_total: u128,
+    pub bump: u8,
+}
+
+impl TipLedger {
+    pub const SPACE: usize = account_space(&[
+        Field::Pubkey, // confession
+        Field::U128,   // sol_total
+        Field::U8,     // bump
+    ]);
+}
+
+/// Engagement counters of a confession frozen at one moment, at
+/// `[b"snapshot", confession, label]`. Never written after creation.
+#[account]
+pub struct Snapshot {
+    pub confession: Pubkey,
+    pub label: u64,
+    pub like_count: u64,
+    pub comment_count: u64,
+    pub tip_events: u32,
+    /// `TipLedger::sol_total` at the time; zero if never tipped.
+    pub sol_total: u128,
+    pub timestamp: i64,
+    pub slot: u64,
+    pub bump: u8,
+}
+
+impl Snapshot {
+    pub const SPACE: usize = account_space(&[
+        Field::Pubkey, // confession
+        Field::U64,    // label
+        Field::U64,    // like_count
+        Field::U64,    // comment_count
+        Field::U32,    // tip_events
+        Field::U128,   // sol_total
+        Field::I64,    // timestamp
+        Field::U64,    // slot
+        Field::U8,     // bump
+    ]);
+}
+
+/// Site-wide totals kept in sync by every counting instruction. The account
+/// is required (not optional) on those instructions so the tallies never
+/// drift from the per-account counters. `total_confessions` counts published
+/// confessions only.
+#[account]
+pub struct GlobalStats {
+    pub total_confessions: u64,
+    pub total_likes: u64,
+    pub total_comments: u64,
+    /// Index handed to the next published confession. Unlike
+    /// `total_confessions` it never goes down, so deletes can't cause an
+    /// index to be handed out twice.
+    pub next_confession_index: u64,
+    pub bump: u8,
+}
+
+impl GlobalStats {
+    pub const SPACE: usize = account_space(&[
+        Field::U64, // total_confessions
+        Field::U64, // total_likes
+        Field::U64, // total_comments
+        Field::U64, // next_confession_index
+        Field::U8,  // bump
+    ]);
+}
+
+// ============================================
+// CONTEXT STRUCTURES
+// ============================================
+
+#[derive(Accounts)]
 #[instruction(content_uri: String)]
 pub struct CreateConfession<'info> {
     #[account(
         init,
-        payer = author,
+        payer = payer,
         space = ConfessionAccount::SPACE,
         seeds = [
-            b"confession",
+            CONFESSION_SEED,
             author.key().as_ref(),
         ],
         bump
     )]
     pub confession: Account<'info, ConfessionAccount>,
 
+    #[account(mut, seeds = [STATS_SEED], bump = stats.bump)]
+    pub stats: Account<'info, GlobalStats>,
+
+    pub author: Signer<'info>,
+
+    /// Funds the account's rent. Pass the author again to pay for yourself.
     #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Required when `quoted_comment` is passed; must be that comment.
+    #[account(mut)]
+    pub quoted_comment: Option<Account<'info, CommentAccount>>,
+
+    /// The quoted comment's confession; required together with it.
+    pub quoted_confession: Option<Account<'info, ConfessionAccount>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateDeadmanConfession<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = ConfessionAccount::SPACE,
+        seeds = [
+            CONFESSION_SEED,
+            author.key().as_ref(),
+        ],
+        bump
+    )]
+    pub confession: Account<'info, ConfessionAccount>,
+
     pub author: Signer<'info>,
 
+    /// Funds the account's rent and the release bounty.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct Checkin<'info> {
+    #[account(mut, has_one = author @ WhisperError::NotConfessionAuthor)]
+    pub confession: Account<'info, ConfessionAccount>,
+
+    pub author: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TriggerRelease<'info> {
+    #[account(mut)]
+    pub confession: Account<'info, ConfessionAccount>,
+
+    #[account(mut, seeds = [STATS_SEED], bump = stats.bump)]
+    pub stats: Account<'info, GlobalStats>,
+
+    #[account(mut)]
+    pub cranker: SystemAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct PublishConfession<'info> {
+    #[account(mut, has_one = author @ WhisperError::NotConfessionAuthor)]
+    pub confession: Account<'info, ConfessionAccount>,
+
+    #[account(mut, seeds = [STATS_SEED], bump = stats.bump)]
+    pub stats: Account<'info, GlobalStats>,
+
+    pub author: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ActivateScheduledConfession<'info> {
+    #[account(mut)]
+    pub confession: Account<'info, ConfessionAccount>,
+
+    #[account(mut, seeds = [STATS_SEED], bump = stats.bump)]
+    pub stats: Account<'info, GlobalStats>,
+}
+
 #[derive(Accounts)]
 pub struct LikeConfession<'info> {
     #[account(mut)]
     pub confession: Account<'info, ConfessionAccount>,
 
+    #[account(mut, seeds = [STATS_SEED], bump = stats.bump)]
+    pub stats: Account<'info, GlobalStats>,
+
     pub user: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct LikeConfessionWithMemo<'info> {
+    #[account(mut)]
+    pub confession: Account<'info, ConfessionAccount>,
+
+    #[account(mut, seeds = [STATS_SEED], bump = stats.bump)]
+    pub stats: Account<'info, GlobalStats>,
+
+    pub user: Signer<'info>,
+
+    /// CHECK: only used as the CPI target; the address is pinned to SPL Memo.
+    #[account(address = MEMO_PROGRAM_ID @ WhisperError::InvalidMemoProgram)]
+    pub memo_program: UncheckedAccount<'info>,
+}
+
 #[derive(Accounts)]
 #[instruction(content_uri: String)]
 pub struct CommentConfession<'info> {
@@ -195,10 +1617,10 @@ pub struct CommentConfession<'info> {
 
     #[account(
         init,
-        payer = commenter,
+        payer = payer,
         space = CommentAccount::SPACE,
         seeds = [
-            b"comment",
+            COMMENT_SEED,
             confession.key().as_ref(),
             commenter.key().as_ref(),
         ],
@@ -206,31 +1628,271 @@ pub struct CommentConfession<'info> {
     )]
     pub comment: Account<'info, CommentAccount>,
 
-    #[account(mut)]
+    #[account(mut, seeds = [STATS_SEED], bump = stats.bump)]
+    pub stats: Account<'info, GlobalStats>,
+
     pub commenter: Signer<'info>,
 
+    /// Funds the account's rent. Pass the commenter again to pay for yourself.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct DeleteConfession {
-    // TODO: Define keys needed for closing account
-    // Hint: mutation access to confession, author as receiver
+pub struct DeleteConfession<'info> {
+    #[account(
+        mut,
+        has_one = author @ WhisperError::NotConfessionAuthor,
+        has_one = rent_payer @ WhisperError::RentPayerMismatch,
+        close = rent_payer
+    )]
+    pub confession: Account<'info, ConfessionAccount>,
+
+    /// CHECK: may not be initialized yet; closed only when program-owned.
+    #[account(mut, seeds = [TIP_LEDGER_SEED, confession.key().as_ref()], bump)]
+    pub tip_ledger: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [STATS_SEED], bump = stats.bump)]
+    pub stats: Account<'info, GlobalStats>,
+
+    pub author: Signer<'info>,
+
+    /// Also receives the `TipLedger` rent, whoever paid it.
+    #[account(mut)]
+    pub rent_payer: SystemAccount<'info>,
+
+    /// Required when the confession quotes a comment; must be that comment.
+    #[account(mut)]
+    pub quoted_comment: Option<Account<'info, CommentAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct CloseLegacyAccount<'info> {
+    /// CHECK: a legacy-layout confession or comment; owner, discriminator
+    /// and length are checked in the handler.
+    #[account(mut)]
+    pub legacy: UncheckedAccount<'info>,
+
+    /// The legacy account's author or commenter; receives its rent.
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SoftDeleteComment<'info> {
+    #[account(mut, has_one = commenter @ WhisperError::NotCommenter)]
+    pub comment: Account<'info, CommentAccount>,
+
+    pub commenter: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct PurgeComment<'info> {
+    #[account(mut)]
+    pub confession: Account<'info, ConfessionAccount>,
+
+    #[account(
+        mut,
+        has_one = confession,
+        has_one = commenter @ WhisperError::NotCommenter,
+        has_one = rent_payer @ WhisperError::RentPayerMismatch,
+        close = rent_payer
+    )]
+    pub comment: Account<'info, CommentAccount>,
+
+    #[account(mut, seeds = [STATS_SEED], bump = stats.bump)]
+    pub stats: Account<'info, GlobalStats>,
+
+    pub commenter: Signer<'info>,
+
+    #[account(mut)]
+    pub rent_payer: SystemAccount<'info>,
+
+    /// Required unless `comment` is the chain head: the comment whose
+    /// `prev_comment` is `comment`.
+    #[account(mut)]
+    pub next_comment: Option<Account<'info, CommentAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct CloseOrphanComment<'info> {
+    /// CHECK: the comment's confession address; empty once deleted, or
+    /// holding a newer confession. Checked in the handler.
+    #[account(address = comment.confession)]
+    pub confession: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        has_one = rent_payer @ WhisperError::RentPayerMismatch,
+        close = rent_payer
+    )]
+    pub comment: Account<'info, CommentAccount>,
+
+    #[account(mut)]
+    pub rent_payer: SystemAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FreezeComments<'info> {
+    #[account(
+        mut,
+        constraint = confession.is_collaborator(&collaborator.key())
+            @ WhisperError::NotConfessionCollaborator
+    )]
+    pub confession: Account<'info, ConfessionAccount>,
+
+    /// The author or a co-author.
+    pub collaborator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AddContentWarning<'info> {
+    #[account(
+        mut,
+        constraint = confession.is_collaborator(&collaborator.key())
+            @ WhisperError::NotConfessionCollaborator
+    )]
+    pub confession: Account<'info, ConfessionAccount>,
+
+    /// The author or a co-author.
+    pub collaborator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ThawComments<'info> {
+    #[account(
+        mut,
+        constraint = confession.is_collaborator(&collaborator.key())
+            @ WhisperError::NotConfessionCollaborator
+    )]
+    pub confession: Account<'info, ConfessionAccount>,
+
+    /// The author or a co-author.
+    pub collaborator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AddCoauthor<'info> {
+    #[account(mut, has_one = author @ WhisperError::NotConfessionAuthor)]
+    pub confession: Account<'info, ConfessionAccount>,
+
+    pub author: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimConfession<'info> {
+    #[account(mut, has_one = author @ WhisperError::NotConfessionAuthor)]
+    pub confession: Account<'info, ConfessionAccount>,
+
+    pub author: Signer<'info>,
+
+    pub claimer: Signer<'info>,
+}
+
+/// The confessions are passed as writable `remaining_accounts`.
+#[derive(Accounts)]
+pub struct ClaimConfessions<'info> {
+    pub author: Signer<'info>,
+
+    pub claimer: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct DislikeConfession {
-    // TODO: Define keys needed for liking logic
+pub struct RemoveCoauthor<'info> {
+    #[account(mut, has_one = author @ WhisperError::NotConfessionAuthor)]
+    pub confession: Account<'info, ConfessionAccount>,
+
+    pub author: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct EditConfession {
-    // TODO: Define keys needed for editing logic
+pub struct DislikeConfession<'info> {
+    // TODO: Define keys needed for liking logic. The signer keeps the
+    // `'info` lifetime the CPI client codegen expects.
+    pub user: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct TipAuthor {
-    // TODO: Define keys needed for tipping logic (tipper, author, etc)
+pub struct EditConfession<'info> {
+    #[account(
+        mut,
+        constraint = confession.is_collaborator(&collaborator.key())
+            @ WhisperError::NotConfessionCollaborator
+    )]
+    pub confession: Account<'info, ConfessionAccount>,
+
+    /// The author or a co-author.
+    pub collaborator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TipAuthor<'info> {
+    #[account(mut)]
+    pub confession: Account<'info, ConfessionAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = tipper,
+        space = TipLedger::SPACE,
+        seeds = [TIP_LEDGER_SEED, confession.key().as_ref()],
+        bump
+    )]
+    pub tip_ledger: Account<'info, TipLedger>,
+
+    #[account(mut, seeds = [STATS_SEED], bump = stats.bump)]
+    pub stats: Account<'info, GlobalStats>,
+
+    #[account(mut, address = confession.author @ WhisperError::TipRecipientMismatch)]
+    pub author: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub tipper: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct TipComment<'info> {
+    #[account(mut)]
+    pub comment: Account<'info, CommentAccount>,
+
+    /// The comment's confession, whose activity window the tip bumps.
+    #[account(mut, address = comment.confession)]
+    pub confession: Account<'info, ConfessionAccount>,
+
+    #[account(mut, address = comment.commenter @ WhisperError::TipRecipientMismatch)]
+    pub commenter: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub tipper: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(label: u64)]
+pub struct SnapshotConfession<'info> {
+    pub confession: Account<'info, ConfessionAccount>,
+
+    /// CHECK: may not be initialized yet; read only when program-owned.
+    #[account(seeds = [TIP_LEDGER_SEED, confession.key().as_ref()], bump)]
+    pub tip_ledger: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = Snapshot::SPACE,
+        seeds = [SNAPSHOT_SEED, confession.key().as_ref(), &label.to_le_bytes()],
+        bump
+    )]
+    pub snapshot: Account<'info, Snapshot>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
@@ -239,7 +1901,7 @@ pub struct InitializeUserCounter<'info> {
         init,
         payer = user,
         space = UserCounter::SPACE,
-        seeds = [b"user_counter", user.key().as_ref()],
+        seeds = [USER_COUNTER_SEED, user.key().as_ref()],
         bump
     )]
     pub user_counter: Account<'info, UserCounter>,
@@ -250,21 +1912,581 @@ pub struct InitializeUserCounter<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct InitializeGlobalStats<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = GlobalStats::SPACE,
+        seeds = [STATS_SEED],
+        bump
+    )]
+    pub stats: Account<'info, GlobalStats>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// ============================================
+// EVENTS
+// ============================================
+
+#[event]
+pub struct ConfessionCreated {
+    pub confession: Pubkey,
+    pub author: Pubkey,
+    pub original_author: Pubkey,
+    pub content_warnings: u16,
+    pub content_standard: u8,
+    pub quoted_comment: Option<Pubkey>,
+    pub timestamp: i64,
+    pub slot: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DeletionReason {
+    /// The author or commenter removed their own content.
+    Author,
+    /// Reserved for admin takedowns once moderation instructions exist.
+    Moderation,
+    /// The comment's confession was deleted; see `close_orphan_comment`.
+    Orphaned,
+}
+
+#[event]
+pub struct ConfessionDeleted {
+    pub confession: Pubkey,
+    pub author: Pubkey,
+    pub like_count: u64,
+    pub comment_count: u64,
+    pub reason: DeletionReason,
+    pub timestamp: i64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct CommentDeleted {
+    pub comment: Pubkey,
+    pub confession: Pubkey,
+    pub commenter: Pubkey,
+    pub quote_count: u32,
+    pub total_tips: u64,
+    /// False for `soft_delete_comment`, which keeps the account.
+    pub closed: bool,
+    pub reason: DeletionReason,
+    pub timestamp: i64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct ContentWarningAdded {
+    pub confession: Pubkey,
+    /// The bits passed in, some of which may already have been set.
+    pub added: u16,
+    /// The full bitfield after the update.
+    pub content_warnings: u16,
+    pub by: Pubkey,
+    pub slot: u64,
+}
+
+#[event]
+pub struct ConfessionClaimed {
+    pub confession: Pubkey,
+    pub previous_author: Pubkey,
+    pub new_author: Pubkey,
+    pub slot: u64,
+}
+
+#[event]
+pub struct ConfessionPublished {
+    pub confession: Pubkey,
+    pub author: Pubkey,
+    /// Global index assigned at publish; see `GlobalStats::next_confession_index`.
+    pub index: u64,
+    pub published_at: i64,
+    pub slot: u64,
+}
+
+/// Carries enough of the comment chain for a client to page a thread from
+/// events alone: `prev_comment` is the head this comment replaced.
+#[event]
+pub struct CommentAdded {
+    pub confession: Pubkey,
+    pub comment: Pubkey,
+    pub commenter: Pubkey,
+    /// Zero-based position in the thread; reused after a purge.
+    pub comment_index: u64,
+    pub prev_comment: Option<Pubkey>,
+    /// The confession's `comment_count` including this comment.
+    pub comment_count: u64,
+    pub content_standard: u8,
+    pub timestamp: i64,
+    pub slot: u64,
+}
+
+/// Emitted when `purge_comment` unlinks a comment. Soft deletes keep the
+/// chain intact and don't emit this.
+#[event]
+pub struct CommentChainRepaired {
+    pub confession: Pubkey,
+    pub removed_comment: Pubkey,
+    /// The newer comment now pointing at the removed comment's
+    /// `prev_comment`, or `None` when the head was removed.
+    pub relinked_comment: Option<Pubkey>,
+    /// The head after the purge.
+    pub latest_comment: Option<Pubkey>,
+    pub comment_count: u64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct CommentTipped {
+    pub comment: Pubkey,
+    pub confession: Pubkey,
+    pub commenter: Pubkey,
+    pub tipper: Pubkey,
+    pub amount: u64,
+    pub total_tips: u64,
+    pub slot: u64,
+}
+
+/// Emitted once per 25/50/75/100% threshold a tip crosses. A single large
+/// tip can cross several and emit one event for each.
+#[event]
+pub struct TipGoalProgress {
+    pub confession: Pubkey,
+    pub percent: u8,
+    pub total_tips_lamports: u128,
+    pub tip_goal_lamports: u64,
+    pub timestamp: i64,
+    pub slot: u64,
+}
+
+// ============================================
+// RETURN DATA
+// ============================================
+
+/// Return data of `create_confession` and `comment_confession`, Borsh
+/// encoded. CPI callers read it with `get_return_data`; clients find it in
+/// the `Program return:` log of a simulated transaction.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CreatedAccount {
+    /// The new confession or comment.
+    pub address: Pubkey,
+    /// For a comment, its zero-based position in the confession's thread.
+    /// For a confession, the global index from
+    /// `GlobalStats::next_confession_index`, or `None` for drafts, scheduled
+    /// and dormant confessions. Those get an index only once published,
+    /// reported by `ConfessionPublished`.
+    pub index: Option<u64>,
+    pub timestamp: i64,
+}
+
 // ============================================
 // ERROR DEFINITIONS
 // ============================================
 
+/// Codes are grouped into bands of 100 and pinned with explicit
+/// discriminants, so clients matching on numbers keep working across
+/// upgrades. Codes are append-only: add new variants at the end of their
+/// band with the next free number, and never reuse or renumber one. The
+/// frozen table in `sdk::error_codes` fails the tests if a code moves.
 #[error_code]
 pub enum WhisperError {
+    // Validation: malformed or out-of-range arguments, 6000-6099
     #[msg("Content URI exceeds maximum allowed length")]
-    ContentUriTooLong,
-    
+    ContentUriTooLong = 0,
+
     #[msg("Content URI cannot be empty")]
-    EmptyContentUri,
-    
+    EmptyContentUri = 1,
+
+    #[msg("Stored URI length exceeds the URI buffer")]
+    InvalidUriLength = 2,
+
+    #[msg("Stored URI is not valid UTF-8")]
+    InvalidUriEncoding = 3,
+
+    #[msg("Clock sysvar returned a zero timestamp")]
+    InvalidClockTimestamp = 4,
+
+    #[msg("Content URI must be printable ASCII without whitespace")]
+    InvalidUriCharacter = 5,
+
+    #[msg("Content URI fragment exceeds maximum allowed length")]
+    UriFragmentTooLong = 6,
+
+    #[msg("Memo cannot be empty")]
+    EmptyMemo = 7,
+
+    #[msg("Memo exceeds maximum allowed length")]
+    MemoTooLong = 8,
+
+    #[msg("Memo program does not match the SPL Memo program id")]
+    InvalidMemoProgram = 9,
+
+    #[msg("Quoted comment account does not match the quoted_comment argument")]
+    QuotedCommentMismatch = 10,
+
+    #[msg("Tip amount must be greater than zero")]
+    InvalidTipAmount = 11,
+
+    #[msg("Freeze duration must be greater than zero")]
+    InvalidFreezeDuration = 12,
+
+    #[msg("Tip goal must be greater than zero")]
+    InvalidTipGoal = 13,
+
+    #[msg("Cannot publish immediately and schedule at the same time")]
+    InvalidPublishSchedule = 14,
+
+    #[msg("Co-author is already a collaborator or the default key")]
+    InvalidCoauthor = 15,
+
+    #[msg("Check-in interval must be greater than zero")]
+    InvalidCheckinInterval = 16,
+
+    #[msg("Content warning bits are empty or undefined")]
+    InvalidContentWarning = 17,
+
+    #[msg("Content standard version is not supported")]
+    UnsupportedContentStandard = 18,
+
+    #[msg("Claimer must be a new, non-default key")]
+    InvalidClaimer = 19,
+
+    #[msg("Next comment does not link to the purged comment")]
+    NextCommentMismatch = 20,
+
+    // Auth: wrong signer or account, 6100-6199
+    #[msg("Signer is not the confession author")]
+    NotConfessionAuthor = 100,
+
+    #[msg("Tip recipient does not match the confession author")]
+    TipRecipientMismatch = 101,
+
+    #[msg("Signer is not the commenter")]
+    NotCommenter = 102,
+
+    #[msg("Rent refund account does not match the recorded rent payer")]
+    RentPayerMismatch = 103,
+
+    #[msg("Signer is neither the author nor a co-author")]
+    NotConfessionCollaborator = 104,
+
+    #[msg("Cannot tip your own comment")]
+    CannotTipSelf = 105,
+
+    // State: not allowed in the account's current state, 6200-6299
+    #[msg("Confession has not been published")]
+    ConfessionNotPublished = 200,
+
+    #[msg("Confession is already published")]
+    ConfessionAlreadyPublished = 201,
+
+    #[msg("Comments on this confession are frozen")]
+    CommentsFrozen = 202,
+
+    #[msg("Comment has been deleted")]
+    CommentDeleted = 203,
+
+    #[msg("Comment is still linked from a newer comment or quoted")]
+    CommentStillReferenced = 204,
+
+    #[msg("Confession is scheduled and not live yet")]
+    ConfessionScheduled = 205,
+
+    #[msg("Confession is not scheduled")]
+    ConfessionNotScheduled = 206,
+
+    #[msg("Co-author not found")]
+    CoauthorNotFound = 207,
+
+    #[msg("Edit window has closed")]
+    EditWindowClosed = 208,
+
+    #[msg("Confession is dormant until its release deadline passes")]
+    ConfessionDormant = 209,
+
+    #[msg("Confession is not dormant")]
+    ConfessionNotDormant = 210,
+
+    #[msg("Release deadline has already passed")]
+    ReleaseDeadlinePassed = 211,
+
+    #[msg("Release deadline has not been reached")]
+    ReleaseDeadlineNotReached = 212,
+
+    #[msg("Comment's confession still exists")]
+    CommentNotOrphaned = 213,
+
+    #[msg("Account is not a legacy-layout confession or comment")]
+    NotLegacyAccount = 214,
+
+    #[msg("Comment belongs to a deleted confession; use close_orphan_comment")]
+    CommentOrphaned = 215,
+
+    #[msg("Account uses the old String-URI layout; reclaim it with close_legacy_account")]
+    LegacyAccountLayout = 216,
+
+    #[msg("Account is smaller than its current layout")]
+    AccountTooSmall = 217,
+
+    // Limits: counters and caps, 6300-6399
     #[msg("Like count overflow")]
-    LikeCountOverflow,
-    
+    LikeCountOverflow = 300,
+
     #[msg("Comment count overflow")]
-    CommentCountOverflow,
-}
\ No newline at end of file
+    CommentCountOverflow = 301,
+
+    #[msg("Confession count overflow")]
+    ConfessionCountOverflow = 302,
+
+    #[msg("Quote count overflow")]
+    QuoteCountOverflow = 303,
+
+    #[msg("Tip ledger overflow")]
+    TipLedgerOverflow = 304,
+
+    #[msg("Freeze duration exceeds the 7 day maximum")]
+    FreezeDurationTooLong = 305,
+
+    #[msg("Confession already has the maximum number of co-authors")]
+    CoauthorLimitReached = 306,
+
+    #[msg("Comment tip total overflow")]
+    CommentTipOverflow = 307,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(byte: u8) -> Pubkey {
+        Pubkey::new_from_array([byte; 32])
+    }
+
+    /// The one complete `ConfessionAccount` literal in the tests. Every field
+    /// has its own non-zero value and both options are `Some`, so the layout
+    /// registry test can tell fields apart and the account fills `SPACE`.
+    pub(crate) fn sample_confession() -> ConfessionAccount {
+        let mut confession = ConfessionAccount {
+            author: key(1),
+            uri: [0; ConfessionAccount::MAX_URI_LENGTH],
+            uri_len: 0,
+            like_count: 3,
+            comment_count: 4,
+            timestamp: 5,
+            slot: 6,
+            status: ConfessionStatus::Scheduled,
+            published_at: 7,
+            tip_events: 8,
+            comments_frozen_until: 9,
+            tip_goal_lamports: 10,
+            goal_reached_at: 11,
+            scheduled_for: 12,
+            rent_payer: key(16),
+            coauthors: [key(17), key(18)],
+            checkin_interval: 19,
+            release_deadline: 20,
+            original_author: key(21),
+            content_warnings: 22,
+            content_standard: 1,
+            last_activity_at: 23,
+            activity_window_start: 24,
+            activity_count_24h: 25,
+            bump: 13,
+            latest_comment: Some(key(14)),
+            quoted_comment: Some(key(15)),
+        };
+        confession.set_content_uri("ar://sample").unwrap();
+        confession
+    }
+
+    /// Same idea as `sample_confession`.
+    pub(crate) fn sample_comment() -> CommentAccount {
+        let mut comment = CommentAccount {
+            confession: key(1),
+            commenter: key(2),
+            uri: [0; CommentAccount::MAX_URI_LENGTH],
+            uri_len: 0,
+            timestamp: 3,
+            slot: 4,
+            quote_count: 5,
+            deleted: true,
+            rent_payer: key(8),
+            total_tips: 9,
+            content_standard: 1,
+            bump: 6,
+            prev_comment: Some(key(7)),
+        };
+        comment.set_content_uri("ar://sample").unwrap();
+        comment
+    }
+
+    /// A freshly created and published confession by `author`, reset the
+    /// same way `create_confession` does it.
+    pub(crate) fn active_confession(author: Pubkey) -> ConfessionAccount {
+        let mut confession = sample_confession();
+        let clock = Clock {
+            unix_timestamp: 1,
+            ..Clock::default()
+        };
+        init_confession(&mut confession, author, author, "ar://sample", &clock, 0).unwrap();
+        confession.status = ConfessionStatus::Active;
+        confession
+    }
+
+    #[test]
+    fn coauthor_slots_fill_reject_and_free() {
+        let author = Pubkey::new_from_array([1; 32]);
+        let mut confession = active_confession(author);
+        let (a, b, c) = (
+            Pubkey::new_from_array([2; 32]),
+            Pubkey::new_from_array([3; 32]),
+            Pubkey::new_from_array([4; 32]),
+        );
+
+        // An empty slot never matches the default key
+        assert!(!confession.is_collaborator(&Pubkey::default()));
+        assert!(confession.is_collaborator(&author));
+
+        confession.add_coauthor(a).unwrap();
+        confession.add_coauthor(b).unwrap();
+        assert!(confession.is_collaborator(&a) && confession.is_collaborator(&b));
+        assert_eq!(
+            confession.add_coauthor(c).unwrap_err(),
+            WhisperError::CoauthorLimitReached.into()
+        );
+
+        for invalid in [a, author, Pubkey::default()] {
+            assert_eq!(
+                confession.add_coauthor(invalid).unwrap_err(),
+                WhisperError::InvalidCoauthor.into()
+            );
+        }
+
+        confession.remove_coauthor(a).unwrap();
+        assert!(!confession.is_collaborator(&a));
+        assert_eq!(
+            confession.remove_coauthor(a).unwrap_err(),
+            WhisperError::CoauthorNotFound.into()
+        );
+        assert_eq!(
+            confession.remove_coauthor(Pubkey::default()).unwrap_err(),
+            WhisperError::CoauthorNotFound.into()
+        );
+        confession.add_coauthor(c).unwrap();
+    }
+
+    #[test]
+    fn activity_window_counts_then_resets_after_a_gap() {
+        let mut confession = active_confession(Pubkey::new_from_array([1; 32]));
+        let start = 1_700_000_000;
+
+        confession.record_activity(start);
+        confession.record_activity(start + ACTIVITY_WINDOW_SECONDS - 1);
+        assert_eq!(confession.activity_window_start, start);
+        assert_eq!(confession.activity_count_24h, 2);
+        assert_eq!(
+            confession.last_activity_at,
+            start + ACTIVITY_WINDOW_SECONDS - 1
+        );
+
+        // A gap of exactly one window, or a far longer one, starts afresh
+        confession.record_activity(start + ACTIVITY_WINDOW_SECONDS);
+        assert_eq!(confession.activity_count_24h, 1);
+        confession.record_activity(start + 100 * ACTIVITY_WINDOW_SECONDS);
+        assert_eq!(
+            confession.activity_window_start,
+            start + 100 * ACTIVITY_WINDOW_SECONDS
+        );
+        assert_eq!(confession.activity_count_24h, 1);
+    }
+
+    #[test]
+    fn activity_window_math_saturates() {
+        let mut confession = active_confession(Pubkey::new_from_array([1; 32]));
+        confession.activity_window_start = i64::MIN + 1;
+        confession.activity_count_24h = u32::MAX;
+
+        // `i64::MAX - (i64::MIN + 1)` would overflow without saturation
+        confession.record_activity(i64::MAX);
+        assert_eq!(confession.activity_window_start, i64::MAX);
+        assert_eq!(confession.activity_count_24h, 1);
+
+        confession.activity_count_24h = u32::MAX;
+        confession.record_activity(i64::MAX);
+        assert_eq!(confession.activity_count_24h, u32::MAX);
+    }
+
+    #[test]
+    fn hand_over_moves_authorship_and_frees_coauthor_slot() {
+        let relayer = Pubkey::new_from_array([1; 32]);
+        let claimer = Pubkey::new_from_array([2; 32]);
+        let mut confession = active_confession(relayer);
+        confession.add_coauthor(claimer).unwrap();
+
+        for invalid in [relayer, Pubkey::default()] {
+            assert_eq!(
+                confession.hand_over(invalid).unwrap_err(),
+                WhisperError::InvalidClaimer.into()
+            );
+        }
+
+        confession.hand_over(claimer).unwrap();
+        assert_eq!(confession.author, claimer);
+        assert_eq!(confession.original_author, relayer);
+        assert!(!confession.is_collaborator(&relayer));
+        // The claimer's old co-author slot is free again
+        assert_eq!(confession.coauthors, [Pubkey::default(); MAX_COAUTHORS]);
+    }
+
+    #[test]
+    fn add_tip_accumulates_past_u64_and_guards_overflow() {
+        let total = add_tip(u64::MAX as u128, u64::MAX).unwrap();
+        assert_eq!(total, 2 * u64::MAX as u128);
+
+        assert!(add_tip(u128::MAX - 1, 1).is_ok());
+        assert_eq!(
+            add_tip(u128::MAX, 1).unwrap_err(),
+            WhisperError::TipLedgerOverflow.into()
+        );
+    }
+
+    #[test]
+    fn goal_quarters_steps_at_each_threshold() {
+        assert_eq!(goal_quarters(0, 1_000), 0);
+        assert_eq!(goal_quarters(249, 1_000), 0);
+        assert_eq!(goal_quarters(250, 1_000), 1);
+        assert_eq!(goal_quarters(500, 1_000), 2);
+        assert_eq!(goal_quarters(999, 1_000), 3);
+        assert_eq!(goal_quarters(1_000, 1_000), 4);
+        // Tipping past the goal stays at 100%
+        assert_eq!(goal_quarters(5_000, 1_000), 4);
+        assert_eq!(goal_quarters(u128::MAX, 1), 4);
+
+        assert_eq!(goal_quarters(1_000, 0), 0);
+    }
+
+    #[test]
+    fn content_uri_round_trips_and_clears_tail() {
+        let mut comment = sample_comment();
+        comment
+            .set_content_uri("https://arweave.net/long-uri")
+            .unwrap();
+        comment.set_content_uri("ar://short").unwrap();
+
+        assert_eq!(comment.content_uri().unwrap(), "ar://short");
+        assert!(comment.uri[10..].iter().all(|b| *b == 0));
+        assert!(comment.set_content_uri(&"a".repeat(201)).is_err());
+
+        comment.uri_len = 201;
+        assert!(comment.content_uri().is_err());
+
+        comment.uri[0] = 0xff;
+        comment.uri_len = 1;
+        assert!(comment.content_uri().is_err());
+    }
+}
@@ -0,0 +1,200 @@
+//! Length-checked loading for account types whose layout has changed.
+//!
+//! `#[account]` deserializes whatever bytes it is given, so an account from
+//! an older build fails with a bare `AccountDidNotDeserialize`. Types listed
+//! with `checked_account!` go through `load_checked` instead, which every
+//! `Account<T>` in every instruction calls before any handler code runs.
+
+use anchor_lang::prelude::*;
+
+use crate::WhisperError;
+
+/// Deserializes `data`, discriminator included, after checking its length
+/// against the current `space`.
+///
+/// Shorter data fails with `LegacyAccountLayout` when it is exactly
+/// `legacy_space`, the size of the `String`-URI build, and with
+/// `AccountTooSmall` otherwise. Longer data loads: Borsh reads the fields it
+/// needs and ignores the trailing bytes, so padded accounts keep working.
+pub fn load_checked<T: AnchorDeserialize>(
+    data: &[u8],
+    discriminator: &[u8],
+    space: usize,
+    legacy_space: usize,
+) -> Result<T> {
+    let given = data
+        .get(..discriminator.len())
+        .ok_or(ErrorCode::AccountDiscriminatorNotFound)?;
+    require!(
+        given == discriminator,
+        ErrorCode::AccountDiscriminatorMismatch
+    );
+
+    if data.len() < space {
+        msg!(
+            "Account is {} bytes, the current layout needs {}",
+            data.len(),
+            space
+        );
+        if data.len() == legacy_space {
+            return err!(WhisperError::LegacyAccountLayout);
+        }
+        return err!(WhisperError::AccountTooSmall);
+    }
+
+    T::deserialize(&mut &data[discriminator.len()..])
+        .map_err(|_| error!(ErrorCode::AccountDidNotDeserialize))
+}
+
+/// The trait impls `#[account]` would generate, with `try_deserialize`
+/// routed through `load_checked`. The type also needs
+/// `#[derive(AnchorSerialize, AnchorDeserialize, Clone)]`, which provides
+/// the IDL type as well.
+///
+/// `try_deserialize_unchecked` stays unchecked: `init` uses it on freshly
+/// allocated, zeroed data.
+macro_rules! checked_account {
+    ($name:ident, discriminator = $discriminator:expr, legacy_space = $legacy_space:expr) => {
+        impl anchor_lang::Discriminator for $name {
+            const DISCRIMINATOR: &'static [u8] = &$discriminator;
+        }
+
+        impl anchor_lang::Owner for $name {
+            fn owner() -> Pubkey {
+                crate::ID
+            }
+        }
+
+        impl anchor_lang::AccountSerialize for $name {
+            fn try_serialize<W: std::io::Write>(&self, writer: &mut W) -> Result<()> {
+                if writer
+                    .write_all(<Self as anchor_lang::Discriminator>::DISCRIMINATOR)
+                    .is_err()
+                {
+                    return Err(ErrorCode::AccountDidNotSerialize.into());
+                }
+                if AnchorSerialize::serialize(self, writer).is_err() {
+                    return Err(ErrorCode::AccountDidNotSerialize.into());
+                }
+                Ok(())
+            }
+        }
+
+        impl anchor_lang::AccountDeserialize for $name {
+            fn try_deserialize(buf: &mut &[u8]) -> Result<Self> {
+                crate::load::load_checked(
+                    buf,
+                    <Self as anchor_lang::Discriminator>::DISCRIMINATOR,
+                    Self::SPACE,
+                    $legacy_space,
+                )
+            }
+
+            fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self> {
+                let discriminator = <Self as anchor_lang::Discriminator>::DISCRIMINATOR;
+                let mut data: &[u8] = &buf[discriminator.len()..];
+                AnchorDeserialize::deserialize(&mut data)
+                    .map_err(|_| ErrorCode::AccountDidNotDeserialize.into())
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::solana_program::hash::hash;
+    use anchor_lang::{AccountDeserialize, AccountSerialize, Discriminator};
+
+    use super::*;
+    use crate::tests::{sample_comment, sample_confession};
+    use crate::{CommentAccount, ConfessionAccount};
+
+    /// Serializes `account` into a buffer of `len` bytes, like an account
+    /// allocated with `len` bytes of data.
+    fn raw<T: AccountSerialize>(account: &T, len: usize) -> Vec<u8> {
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        data.resize(len, 0);
+        data
+    }
+
+    #[test]
+    fn discriminators_match_the_account_attribute() {
+        for (name, discriminator) in [
+            ("ConfessionAccount", ConfessionAccount::DISCRIMINATOR),
+            ("CommentAccount", CommentAccount::DISCRIMINATOR),
+        ] {
+            let expected = hash(format!("account:{name}").as_bytes()).to_bytes();
+            assert_eq!(discriminator, &expected[..8], "{name}");
+        }
+    }
+
+    #[test]
+    fn loads_exact_and_padded_accounts() {
+        let confession = sample_confession();
+        for len in [ConfessionAccount::SPACE, ConfessionAccount::SPACE + 64] {
+            let data = raw(&confession, len);
+            let loaded = ConfessionAccount::try_deserialize(&mut &data[..]).unwrap();
+            assert_eq!(loaded.author, confession.author, "{len} bytes");
+            assert_eq!(
+                loaded.activity_count_24h, confession.activity_count_24h,
+                "{len} bytes"
+            );
+            assert_eq!(
+                loaded.quoted_comment, confession.quoted_comment,
+                "{len} bytes"
+            );
+        }
+
+        let comment = sample_comment();
+        let data = raw(&comment, CommentAccount::SPACE + 1);
+        let loaded = CommentAccount::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(loaded.prev_comment, comment.prev_comment);
+    }
+
+    fn load_error<T: AccountDeserialize>(data: &[u8]) -> Error {
+        match T::try_deserialize(&mut &data[..]) {
+            Ok(_) => panic!("{} bytes loaded", data.len()),
+            Err(error) => error,
+        }
+    }
+
+    #[test]
+    fn undersized_accounts_get_a_descriptive_error() {
+        let confession = raw(&sample_confession(), ConfessionAccount::SPACE);
+        let comment = raw(&sample_comment(), CommentAccount::SPACE);
+
+        assert_eq!(
+            load_error::<ConfessionAccount>(&confession[..ConfessionAccount::LEGACY_SPACE]),
+            WhisperError::LegacyAccountLayout.into()
+        );
+        assert_eq!(
+            load_error::<CommentAccount>(&comment[..CommentAccount::LEGACY_SPACE]),
+            WhisperError::LegacyAccountLayout.into()
+        );
+        for len in [8, 9, ConfessionAccount::SPACE - 1] {
+            assert_eq!(
+                load_error::<ConfessionAccount>(&confession[..len]),
+                WhisperError::AccountTooSmall.into(),
+                "{len} bytes"
+            );
+        }
+        assert_eq!(
+            load_error::<CommentAccount>(&comment[..CommentAccount::SPACE - 1]),
+            WhisperError::AccountTooSmall.into()
+        );
+    }
+
+    #[test]
+    fn other_types_and_short_headers_keep_anchors_errors() {
+        let comment = raw(&sample_comment(), CommentAccount::SPACE);
+        assert_eq!(
+            load_error::<ConfessionAccount>(&comment),
+            ErrorCode::AccountDiscriminatorMismatch.into()
+        );
+        assert_eq!(
+            load_error::<ConfessionAccount>(&comment[..4]),
+            ErrorCode::AccountDiscriminatorNotFound.into()
+        );
+    }
+}
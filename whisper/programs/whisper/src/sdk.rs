@@ -0,0 +1,336 @@
+//! Off-chain helpers for clients, built with the `client` feature.
+//!
+//! Everything here is derived from the same seeds, offsets and error enum the
+//! program uses, so a client that goes through this module cannot drift from
+//! the on-chain layout.
+
+use anchor_lang::prelude::*;
+
+use crate::{
+    CommentAccount, ConfessionAccount, WhisperError, COMMENT_SEED, CONFESSION_SEED, SNAPSHOT_SEED,
+    STATS_SEED, TIP_LEDGER_SEED, USER_COUNTER_SEED,
+};
+
+// ============================================
+// PDA DERIVATION
+// ============================================
+
+/// A creator's confession, `[CONFESSION_SEED, original_author]`. Derive from
+/// `ConfessionAccount::original_author`, never the mutable `author`.
+pub fn confession_pda(original_author: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CONFESSION_SEED, original_author.as_ref()], &crate::ID)
+}
+
+/// A commenter's comment on a confession, `[COMMENT_SEED, confession, commenter]`.
+/// Keyed by the confession's own address, which never changes.
+pub fn comment_pda(confession: &Pubkey, commenter: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[COMMENT_SEED, confession.as_ref(), commenter.as_ref()],
+        &crate::ID,
+    )
+}
+
+/// The program-wide `GlobalStats`, `[STATS_SEED]`.
+pub fn stats_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[STATS_SEED], &crate::ID)
+}
+
+/// A confession's `TipLedger`, `[TIP_LEDGER_SEED, confession]`.
+pub fn tip_ledger_pda(confession: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TIP_LEDGER_SEED, confession.as_ref()], &crate::ID)
+}
+
+/// A user's `UserCounter`, `[USER_COUNTER_SEED, user]`.
+pub fn user_counter_pda(user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[USER_COUNTER_SEED, user.as_ref()], &crate::ID)
+}
+
+/// A labelled `Snapshot` of a confession, `[SNAPSHOT_SEED, confession, label]`
+/// with the label little-endian.
+pub fn snapshot_pda(confession: &Pubkey, label: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[SNAPSHOT_SEED, confession.as_ref(), &label.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+// ============================================
+// MEMCMP FILTER OFFSETS
+// ============================================
+
+/// Byte offsets into `ConfessionAccount` data, discriminator included.
+pub mod confession_offsets {
+    use super::ConfessionAccount;
+
+    pub const AUTHOR: usize = ConfessionAccount::AUTHOR_OFFSET;
+    pub const URI: usize = ConfessionAccount::URI_OFFSET;
+    pub const URI_LEN: usize = ConfessionAccount::URI_LEN_OFFSET;
+    pub const LIKE_COUNT: usize = ConfessionAccount::LIKE_COUNT_OFFSET;
+    pub const COMMENT_COUNT: usize = ConfessionAccount::COMMENT_COUNT_OFFSET;
+    pub const TIMESTAMP: usize = ConfessionAccount::TIMESTAMP_OFFSET;
+    pub const SLOT: usize = ConfessionAccount::SLOT_OFFSET;
+    pub const STATUS: usize = ConfessionAccount::STATUS_OFFSET;
+    pub const PUBLISHED_AT: usize = ConfessionAccount::PUBLISHED_AT_OFFSET;
+    pub const TIP_EVENTS: usize = ConfessionAccount::TIP_EVENTS_OFFSET;
+    pub const COMMENTS_FROZEN_UNTIL: usize = ConfessionAccount::COMMENTS_FROZEN_UNTIL_OFFSET;
+    pub const TIP_GOAL_LAMPORTS: usize = ConfessionAccount::TIP_GOAL_LAMPORTS_OFFSET;
+    pub const GOAL_REACHED_AT: usize = ConfessionAccount::GOAL_REACHED_AT_OFFSET;
+    pub const SCHEDULED_FOR: usize = ConfessionAccount::SCHEDULED_FOR_OFFSET;
+    pub const RENT_PAYER: usize = ConfessionAccount::RENT_PAYER_OFFSET;
+    pub const COAUTHORS: usize = ConfessionAccount::COAUTHORS_OFFSET;
+    pub const CHECKIN_INTERVAL: usize = ConfessionAccount::CHECKIN_INTERVAL_OFFSET;
+    pub const RELEASE_DEADLINE: usize = ConfessionAccount::RELEASE_DEADLINE_OFFSET;
+    pub const ORIGINAL_AUTHOR: usize = ConfessionAccount::ORIGINAL_AUTHOR_OFFSET;
+    pub const CONTENT_WARNINGS: usize = ConfessionAccount::CONTENT_WARNINGS_OFFSET;
+    pub const CONTENT_STANDARD: usize = ConfessionAccount::CONTENT_STANDARD_OFFSET;
+    pub const LAST_ACTIVITY_AT: usize = ConfessionAccount::LAST_ACTIVITY_AT_OFFSET;
+    pub const ACTIVITY_WINDOW_START: usize = ConfessionAccount::ACTIVITY_WINDOW_START_OFFSET;
+    pub const ACTIVITY_COUNT_24H: usize = ConfessionAccount::ACTIVITY_COUNT_24H_OFFSET;
+    pub const BUMP: usize = ConfessionAccount::BUMP_OFFSET;
+    /// `Option` tag byte; the pubkey follows when it is 1.
+    pub const LATEST_COMMENT: usize = ConfessionAccount::LATEST_COMMENT_OFFSET;
+}
+
+/// Byte offsets into `CommentAccount` data, discriminator included.
+pub mod comment_offsets {
+    use super::CommentAccount;
+
+    pub const CONFESSION: usize = CommentAccount::CONFESSION_OFFSET;
+    pub const COMMENTER: usize = CommentAccount::COMMENTER_OFFSET;
+    pub const URI: usize = CommentAccount::URI_OFFSET;
+    pub const URI_LEN: usize = CommentAccount::URI_LEN_OFFSET;
+    pub const TIMESTAMP: usize = CommentAccount::TIMESTAMP_OFFSET;
+    pub const SLOT: usize = CommentAccount::SLOT_OFFSET;
+    pub const QUOTE_COUNT: usize = CommentAccount::QUOTE_COUNT_OFFSET;
+    pub const DELETED: usize = CommentAccount::DELETED_OFFSET;
+    pub const RENT_PAYER: usize = CommentAccount::RENT_PAYER_OFFSET;
+    pub const TOTAL_TIPS: usize = CommentAccount::TOTAL_TIPS_OFFSET;
+    pub const CONTENT_STANDARD: usize = CommentAccount::CONTENT_STANDARD_OFFSET;
+    pub const BUMP: usize = CommentAccount::BUMP_OFFSET;
+    /// `Option` tag byte; the pubkey follows when it is 1.
+    pub const PREV_COMMENT: usize = CommentAccount::PREV_COMMENT_OFFSET;
+}
+
+// ============================================
+// ERROR CODES
+// ============================================
+
+/// Custom program error code of a `WhisperError`, as seen in a failed
+/// transaction's `InstructionError::Custom`.
+pub fn error_code(error: WhisperError) -> u32 {
+    error.into()
+}
+
+/// Numeric `WhisperError` codes, for clients matching on raw error numbers.
+/// This is the frozen table: entries are only ever appended.
+pub mod error_codes {
+    use anchor_lang::error::ERROR_CODE_OFFSET;
+
+    // Validation
+    pub const CONTENT_URI_TOO_LONG: u32 = ERROR_CODE_OFFSET;
+    pub const EMPTY_CONTENT_URI: u32 = ERROR_CODE_OFFSET + 1;
+    pub const INVALID_URI_LENGTH: u32 = ERROR_CODE_OFFSET + 2;
+    pub const INVALID_URI_ENCODING: u32 = ERROR_CODE_OFFSET + 3;
+    pub const INVALID_CLOCK_TIMESTAMP: u32 = ERROR_CODE_OFFSET + 4;
+    pub const INVALID_URI_CHARACTER: u32 = ERROR_CODE_OFFSET + 5;
+    pub const URI_FRAGMENT_TOO_LONG: u32 = ERROR_CODE_OFFSET + 6;
+    pub const EMPTY_MEMO: u32 = ERROR_CODE_OFFSET + 7;
+    pub const MEMO_TOO_LONG: u32 = ERROR_CODE_OFFSET + 8;
+    pub const INVALID_MEMO_PROGRAM: u32 = ERROR_CODE_OFFSET + 9;
+    pub const QUOTED_COMMENT_MISMATCH: u32 = ERROR_CODE_OFFSET + 10;
+    pub const INVALID_TIP_AMOUNT: u32 = ERROR_CODE_OFFSET + 11;
+    pub const INVALID_FREEZE_DURATION: u32 = ERROR_CODE_OFFSET + 12;
+    pub const INVALID_TIP_GOAL: u32 = ERROR_CODE_OFFSET + 13;
+    pub const INVALID_PUBLISH_SCHEDULE: u32 = ERROR_CODE_OFFSET + 14;
+    pub const INVALID_COAUTHOR: u32 = ERROR_CODE_OFFSET + 15;
+    pub const INVALID_CHECKIN_INTERVAL: u32 = ERROR_CODE_OFFSET + 16;
+    pub const INVALID_CONTENT_WARNING: u32 = ERROR_CODE_OFFSET + 17;
+    pub const UNSUPPORTED_CONTENT_STANDARD: u32 = ERROR_CODE_OFFSET + 18;
+    pub const INVALID_CLAIMER: u32 = ERROR_CODE_OFFSET + 19;
+    pub const NEXT_COMMENT_MISMATCH: u32 = ERROR_CODE_OFFSET + 20;
+
+    // Auth
+    pub const NOT_CONFESSION_AUTHOR: u32 = ERROR_CODE_OFFSET + 100;
+    pub const TIP_RECIPIENT_MISMATCH: u32 = ERROR_CODE_OFFSET + 101;
+    pub const NOT_COMMENTER: u32 = ERROR_CODE_OFFSET + 102;
+    pub const RENT_PAYER_MISMATCH: u32 = ERROR_CODE_OFFSET + 103;
+    pub const NOT_CONFESSION_COLLABORATOR: u32 = ERROR_CODE_OFFSET + 104;
+    pub const CANNOT_TIP_SELF: u32 = ERROR_CODE_OFFSET + 105;
+
+    // State
+    pub const CONFESSION_NOT_PUBLISHED: u32 = ERROR_CODE_OFFSET + 200;
+    pub const CONFESSION_ALREADY_PUBLISHED: u32 = ERROR_CODE_OFFSET + 201;
+    pub const COMMENTS_FROZEN: u32 = ERROR_CODE_OFFSET + 202;
+    pub const COMMENT_DELETED: u32 = ERROR_CODE_OFFSET + 203;
+    pub const COMMENT_STILL_REFERENCED: u32 = ERROR_CODE_OFFSET + 204;
+    pub const CONFESSION_SCHEDULED: u32 = ERROR_CODE_OFFSET + 205;
+    pub const CONFESSION_NOT_SCHEDULED: u32 = ERROR_CODE_OFFSET + 206;
+    pub const COAUTHOR_NOT_FOUND: u32 = ERROR_CODE_OFFSET + 207;
+    pub const EDIT_WINDOW_CLOSED: u32 = ERROR_CODE_OFFSET + 208;
+    pub const CONFESSION_DORMANT: u32 = ERROR_CODE_OFFSET + 209;
+    pub const CONFESSION_NOT_DORMANT: u32 = ERROR_CODE_OFFSET + 210;
+    pub const RELEASE_DEADLINE_PASSED: u32 = ERROR_CODE_OFFSET + 211;
+    pub const RELEASE_DEADLINE_NOT_REACHED: u32 = ERROR_CODE_OFFSET + 212;
+    pub const COMMENT_NOT_ORPHANED: u32 = ERROR_CODE_OFFSET + 213;
+    pub const NOT_LEGACY_ACCOUNT: u32 = ERROR_CODE_OFFSET + 214;
+    pub const COMMENT_ORPHANED: u32 = ERROR_CODE_OFFSET + 215;
+    pub const LEGACY_ACCOUNT_LAYOUT: u32 = ERROR_CODE_OFFSET + 216;
+    pub const ACCOUNT_TOO_SMALL: u32 = ERROR_CODE_OFFSET + 217;
+
+    // Limits
+    pub const LIKE_COUNT_OVERFLOW: u32 = ERROR_CODE_OFFSET + 300;
+    pub const COMMENT_COUNT_OVERFLOW: u32 = ERROR_CODE_OFFSET + 301;
+    pub const CONFESSION_COUNT_OVERFLOW: u32 = ERROR_CODE_OFFSET + 302;
+    pub const QUOTE_COUNT_OVERFLOW: u32 = ERROR_CODE_OFFSET + 303;
+    pub const TIP_LEDGER_OVERFLOW: u32 = ERROR_CODE_OFFSET + 304;
+    pub const FREEZE_DURATION_TOO_LONG: u32 = ERROR_CODE_OFFSET + 305;
+    pub const COAUTHOR_LIMIT_REACHED: u32 = ERROR_CODE_OFFSET + 306;
+    pub const COMMENT_TIP_OVERFLOW: u32 = ERROR_CODE_OFFSET + 307;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::error_codes::*;
+    use super::*;
+
+    fn key(byte: u8) -> Pubkey {
+        Pubkey::new_from_array([byte; 32])
+    }
+
+    /// Addresses for `key(1)` as author/user and `key(2)` as commenter,
+    /// derived independently with the same seeds `tests/whisper.ts` uses.
+    /// The "PDA Cross-check" test there pins this table too, so a seed that
+    /// changes on either side fails one of the two suites.
+    #[test]
+    fn pdas_match_pinned_addresses() {
+        let (confession, bump) = confession_pda(&key(1));
+        assert_eq!(
+            (confession, bump),
+            (pubkey!("8m5trZQCdBA25XkzEn5ADxoPphTCnSPdwE6eEygj2KtK"), 255)
+        );
+        assert_eq!(
+            comment_pda(&confession, &key(2)),
+            (pubkey!("8nyF8Bq2xoN6WH362ACmPyoPQzDaKjJMTTGEmXq95icB"), 254)
+        );
+        assert_eq!(
+            stats_pda(),
+            (pubkey!("F18Gz33ABEM9pmPe9NVoScFpFX648t1kgT2Yz8kra9qx"), 255)
+        );
+        assert_eq!(
+            tip_ledger_pda(&confession),
+            (pubkey!("G9VartHcjv5o7XeV9K393BrXouJUvTJscA3BF76gpj2X"), 255)
+        );
+        assert_eq!(
+            user_counter_pda(&key(1)),
+            (pubkey!("21uQStaahXMY3gYqYAfSoP6BiksR9BMibGPkCCPmYVZp"), 255)
+        );
+        assert_eq!(
+            snapshot_pda(&confession, 7),
+            (pubkey!("FrapJCzy87cmqo4RFtVEjS6dCzyxtj4Uf9yRV5gA1C1E"), 254)
+        );
+    }
+
+    #[test]
+    fn comment_pda_is_per_commenter() {
+        let (confession, _) = confession_pda(&key(1));
+        assert_ne!(
+            comment_pda(&confession, &key(2)).0,
+            comment_pda(&confession, &key(3)).0
+        );
+    }
+
+    #[test]
+    fn error_codes_match_enum() {
+        let cases = [
+            (WhisperError::ContentUriTooLong, CONTENT_URI_TOO_LONG),
+            (WhisperError::EmptyContentUri, EMPTY_CONTENT_URI),
+            (WhisperError::InvalidUriLength, INVALID_URI_LENGTH),
+            (WhisperError::InvalidUriEncoding, INVALID_URI_ENCODING),
+            (WhisperError::InvalidClockTimestamp, INVALID_CLOCK_TIMESTAMP),
+            (WhisperError::InvalidUriCharacter, INVALID_URI_CHARACTER),
+            (WhisperError::UriFragmentTooLong, URI_FRAGMENT_TOO_LONG),
+            (WhisperError::EmptyMemo, EMPTY_MEMO),
+            (WhisperError::MemoTooLong, MEMO_TOO_LONG),
+            (WhisperError::InvalidMemoProgram, INVALID_MEMO_PROGRAM),
+            (WhisperError::QuotedCommentMismatch, QUOTED_COMMENT_MISMATCH),
+            (WhisperError::InvalidTipAmount, INVALID_TIP_AMOUNT),
+            (WhisperError::InvalidFreezeDuration, INVALID_FREEZE_DURATION),
+            (WhisperError::InvalidTipGoal, INVALID_TIP_GOAL),
+            (
+                WhisperError::InvalidPublishSchedule,
+                INVALID_PUBLISH_SCHEDULE,
+            ),
+            (WhisperError::InvalidCoauthor, INVALID_COAUTHOR),
+            (
+                WhisperError::InvalidCheckinInterval,
+                INVALID_CHECKIN_INTERVAL,
+            ),
+            (WhisperError::InvalidContentWarning, INVALID_CONTENT_WARNING),
+            (
+                WhisperError::UnsupportedContentStandard,
+                UNSUPPORTED_CONTENT_STANDARD,
+            ),
+            (WhisperError::InvalidClaimer, INVALID_CLAIMER),
+            (WhisperError::NextCommentMismatch, NEXT_COMMENT_MISMATCH),
+            (WhisperError::NotConfessionAuthor, NOT_CONFESSION_AUTHOR),
+            (WhisperError::TipRecipientMismatch, TIP_RECIPIENT_MISMATCH),
+            (WhisperError::NotCommenter, NOT_COMMENTER),
+            (WhisperError::RentPayerMismatch, RENT_PAYER_MISMATCH),
+            (
+                WhisperError::NotConfessionCollaborator,
+                NOT_CONFESSION_COLLABORATOR,
+            ),
+            (WhisperError::CannotTipSelf, CANNOT_TIP_SELF),
+            (
+                WhisperError::ConfessionNotPublished,
+                CONFESSION_NOT_PUBLISHED,
+            ),
+            (
+                WhisperError::ConfessionAlreadyPublished,
+                CONFESSION_ALREADY_PUBLISHED,
+            ),
+            (WhisperError::CommentsFrozen, COMMENTS_FROZEN),
+            (WhisperError::CommentDeleted, COMMENT_DELETED),
+            (
+                WhisperError::CommentStillReferenced,
+                COMMENT_STILL_REFERENCED,
+            ),
+            (WhisperError::ConfessionScheduled, CONFESSION_SCHEDULED),
+            (
+                WhisperError::ConfessionNotScheduled,
+                CONFESSION_NOT_SCHEDULED,
+            ),
+            (WhisperError::CoauthorNotFound, COAUTHOR_NOT_FOUND),
+            (WhisperError::EditWindowClosed, EDIT_WINDOW_CLOSED),
+            (WhisperError::ConfessionDormant, CONFESSION_DORMANT),
+            (WhisperError::ConfessionNotDormant, CONFESSION_NOT_DORMANT),
+            (WhisperError::ReleaseDeadlinePassed, RELEASE_DEADLINE_PASSED),
+            (
+                WhisperError::ReleaseDeadlineNotReached,
+                RELEASE_DEADLINE_NOT_REACHED,
+            ),
+            (WhisperError::CommentNotOrphaned, COMMENT_NOT_ORPHANED),
+            (WhisperError::NotLegacyAccount, NOT_LEGACY_ACCOUNT),
+            (WhisperError::CommentOrphaned, COMMENT_ORPHANED),
+            (WhisperError::LegacyAccountLayout, LEGACY_ACCOUNT_LAYOUT),
+            (WhisperError::AccountTooSmall, ACCOUNT_TOO_SMALL),
+            (WhisperError::LikeCountOverflow, LIKE_COUNT_OVERFLOW),
+            (WhisperError::CommentCountOverflow, COMMENT_COUNT_OVERFLOW),
+            (
+                WhisperError::ConfessionCountOverflow,
+                CONFESSION_COUNT_OVERFLOW,
+            ),
+            (WhisperError::QuoteCountOverflow, QUOTE_COUNT_OVERFLOW),
+            (WhisperError::TipLedgerOverflow, TIP_LEDGER_OVERFLOW),
+            (
+                WhisperError::FreezeDurationTooLong,
+                FREEZE_DURATION_TOO_LONG,
+            ),
+            (WhisperError::CoauthorLimitReached, COAUTHOR_LIMIT_REACHED),
+            (WhisperError::CommentTipOverflow, COMMENT_TIP_OVERFLOW),
+        ];
+        for (error, code) in cases {
+            assert_eq!(error_code(error), code, "{}", error.name());
+        }
+    }
+}
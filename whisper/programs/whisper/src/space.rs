@@ -0,0 +1,127 @@
+/// Anchor's account discriminator prefix.
+pub const DISCRIMINATOR: usize = 8;
+
+/// Borsh-serialized width of one account field. Account `SPACE` constants
+/// list their fields in declaration order and sum them with
+/// `account_space`, so adding a field without updating `SPACE` shows up as
+/// an obvious omission from the list (and fails the tests below).
+#[derive(Clone, Copy)]
+pub enum Field {
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    I64,
+    U128,
+    Pubkey,
+    /// Fieldless enum, serialized as a single tag byte.
+    Enum,
+    /// Fixed-size byte array.
+    Bytes(usize),
+    /// `Option<Pubkey>`, sized for the `Some` case.
+    OptionPubkey,
+    /// Fixed-size `[Pubkey; N]`.
+    Pubkeys(usize),
+}
+
+impl Field {
+    pub const fn size(self) -> usize {
+        match self {
+            Field::Bool | Field::U8 | Field::Enum => 1,
+            Field::U16 => 2,
+            Field::U32 => 4,
+            Field::U64 | Field::I64 => 8,
+            Field::U128 => 16,
+            Field::Pubkey => 32,
+            Field::Bytes(len) => len,
+            Field::OptionPubkey => 1 + 32,
+            Field::Pubkeys(len) => 32 * len,
+        }
+    }
+}
+
+/// Total account size, discriminator included.
+pub const fn account_space(fields: &[Field]) -> usize {
+    let mut total = DISCRIMINATOR;
+    let mut i = 0;
+    while i < fields.len() {
+        total += fields[i].size();
+        i += 1;
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{sample_comment, sample_confession};
+    use crate::*;
+
+    fn key(byte: u8) -> Pubkey {
+        Pubkey::new_from_array([byte; 32])
+    }
+
+    /// Serializes without the discriminator, which `SPACE` also counts.
+    fn assert_exact_fit<T: AnchorSerialize>(account: &T, space: usize) {
+        let data = account.try_to_vec().unwrap();
+        assert_eq!(data.len(), space - DISCRIMINATOR);
+    }
+
+    #[test]
+    fn confession_account_fits_space() {
+        assert_exact_fit(&sample_confession(), ConfessionAccount::SPACE);
+    }
+
+    #[test]
+    fn comment_account_fits_space() {
+        assert_exact_fit(&sample_comment(), CommentAccount::SPACE);
+    }
+
+    #[test]
+    fn user_counter_fits_space() {
+        let counter = UserCounter {
+            count: u64::MAX,
+            bump: u8::MAX,
+        };
+        assert_exact_fit(&counter, UserCounter::SPACE);
+    }
+
+    #[test]
+    fn global_stats_fits_space() {
+        let stats = GlobalStats {
+            total_confessions: u64::MAX,
+            total_likes: u64::MAX,
+            total_comments: u64::MAX,
+            next_confession_index: u64::MAX,
+            bump: u8::MAX,
+        };
+        assert_exact_fit(&stats, GlobalStats::SPACE);
+    }
+
+    #[test]
+    fn tip_ledger_fits_space() {
+        let ledger = TipLedger {
+            confession: key(1),
+            sol_total: u128::MAX,
+            bump: u8::MAX,
+        };
+        assert_exact_fit(&ledger, TipLedger::SPACE);
+    }
+
+    #[test]
+    fn snapshot_fits_space() {
+        let snapshot = Snapshot {
+            confession: key(1),
+            label: u64::MAX,
+            like_count: u64::MAX,
+            comment_count: u64::MAX,
+            tip_events: u32::MAX,
+            sol_total: u128::MAX,
+            timestamp: i64::MAX,
+            slot: u64::MAX,
+            bump: u8::MAX,
+        };
+        assert_exact_fit(&snapshot, Snapshot::SPACE);
+    }
+}
@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+
+use crate::{ConfessionAccount, ConfessionStatus, WhisperError};
+
+/// Shared lifecycle rules for every instruction that interacts with a
+/// confession: likes, comments, tips and quotes.
+///
+/// The confession must be `Active`, or `Scheduled` with its `scheduled_for`
+/// time reached. Every other state keeps its own error so clients can tell a
+/// draft from a confession that is merely not live yet.
+///
+/// This only checks; callers that take the confession mutably run
+/// `activate_if_due` first so a due schedule is also recorded as published.
+pub fn assert_interactable(confession: &ConfessionAccount, clock: &Clock) -> Result<()> {
+    match confession.status {
+        ConfessionStatus::Active => Ok(()),
+        ConfessionStatus::Draft => err!(WhisperError::ConfessionNotPublished),
+        ConfessionStatus::Scheduled if clock.unix_timestamp >= confession.scheduled_for => Ok(()),
+        ConfessionStatus::Scheduled => err!(WhisperError::ConfessionScheduled),
+        ConfessionStatus::Dormant => err!(WhisperError::ConfessionDormant),
+    }
+}
+
+/// `assert_interactable` plus the comment-only rule: comments must not be
+/// frozen at `clock`. Likes and tips are unaffected by a freeze.
+pub fn assert_commentable(confession: &ConfessionAccount, clock: &Clock) -> Result<()> {
+    assert_interactable(confession, clock)?;
+    require!(
+        clock.unix_timestamp >= confession.comments_frozen_until,
+        WhisperError::CommentsFrozen
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::active_confession;
+
+    const NOW: i64 = 1_700_000_000;
+
+    fn clock() -> Clock {
+        Clock {
+            unix_timestamp: NOW,
+            ..Clock::default()
+        }
+    }
+
+    #[derive(Clone, Copy, Debug)]
+    enum Freeze {
+        Thawed,
+        Expired,
+        Frozen,
+    }
+
+    fn confession(
+        status: ConfessionStatus,
+        scheduled_for: i64,
+        freeze: Freeze,
+    ) -> ConfessionAccount {
+        let mut confession = active_confession(Pubkey::new_from_array([1; 32]));
+        confession.status = status;
+        confession.scheduled_for = scheduled_for;
+        confession.comments_frozen_until = match freeze {
+            Freeze::Thawed => 0,
+            // The deadline itself is already open again
+            Freeze::Expired => NOW,
+            Freeze::Frozen => NOW + 1,
+        };
+        confession
+    }
+
+    #[test]
+    fn status_and_freeze_matrix() {
+        use ConfessionStatus::*;
+
+        let states: [(ConfessionStatus, i64, Option<WhisperError>); 6] = [
+            (Active, 0, None),
+            (Draft, 0, Some(WhisperError::ConfessionNotPublished)),
+            (Scheduled, NOW + 1, Some(WhisperError::ConfessionScheduled)),
+            (Scheduled, NOW, None),
+            (Scheduled, NOW - 1, None),
+            (Dormant, 0, Some(WhisperError::ConfessionDormant)),
+        ];
+
+        for (status, scheduled_for, state_error) in states {
+            for freeze in [Freeze::Thawed, Freeze::Expired, Freeze::Frozen] {
+                let case = format!("{status:?} scheduled_for={scheduled_for} {freeze:?}");
+                let confession = confession(status, scheduled_for, freeze);
+
+                // A freeze never blocks likes, tips or quotes
+                let interact = assert_interactable(&confession, &clock());
+                match state_error {
+                    None => assert!(interact.is_ok(), "{case}"),
+                    Some(error) => assert_eq!(interact.unwrap_err(), error.into(), "{case}"),
+                }
+
+                // The state error wins over a freeze
+                let comment_error = state_error.or(match freeze {
+                    Freeze::Frozen => Some(WhisperError::CommentsFrozen),
+                    _ => None,
+                });
+                let comment = assert_commentable(&confession, &clock());
+                match comment_error {
+                    None => assert!(comment.is_ok(), "{case}"),
+                    Some(error) => assert_eq!(comment.unwrap_err(), error.into(), "{case}"),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,239 @@
+use anchor_lang::{prelude::*, Discriminator};
+
+use crate::{content_warning, standards, CommentAccount, ConfessionAccount, WhisperError};
+
+/// Longest `#fragment` (excluding the `#`) accepted on a content URI.
+pub const MAX_URI_FRAGMENT_LENGTH: usize = 64;
+
+/// Shared URI rules for every instruction that stores a content URI.
+///
+/// The URI must be non-empty, at most `max_len` bytes, made only of
+/// printable ASCII with no whitespace, and carry a fragment no longer than
+/// `MAX_URI_FRAGMENT_LENGTH`.
+///
+/// Length is measured in UTF-8 bytes, the same unit as the account buffer,
+/// and is checked before the character rules so an over-long multi-byte URI
+/// reports `ContentUriTooLong` rather than a charset error.
+pub fn validate_uri(uri: &str, max_len: usize) -> Result<()> {
+    require!(!uri.is_empty(), WhisperError::EmptyContentUri);
+    if uri.len() > max_len {
+        msg!("Content URI is {} bytes, limit is {}", uri.len(), max_len);
+        return err!(WhisperError::ContentUriTooLong);
+    }
+    require!(
+        uri.bytes().all(|b| b.is_ascii_graphic()),
+        WhisperError::InvalidUriCharacter
+    );
+
+    if let Some((_, fragment)) = uri.split_once('#') {
+        require!(
+            fragment.len() <= MAX_URI_FRAGMENT_LENGTH,
+            WhisperError::UriFragmentTooLong
+        );
+    }
+
+    Ok(())
+}
+
+/// Rejects bits outside `content_warning::ALL`. Zero is valid: no warnings.
+pub fn validate_content_warnings(bits: u16) -> Result<()> {
+    require!(
+        bits & !content_warning::ALL == 0,
+        WhisperError::InvalidContentWarning
+    );
+    Ok(())
+}
+
+/// Accepts any `standards` version up to `standards::LATEST`.
+pub fn validate_content_standard(version: u8) -> Result<()> {
+    require!(
+        version <= standards::LATEST,
+        WhisperError::UnsupportedContentStandard
+    );
+    Ok(())
+}
+
+/// Checks that `data` is a confession or comment in the pre-fixed-URI
+/// layout and that `signer` is its author or commenter.
+///
+/// The discriminator picks the type; the data must be shorter than that
+/// type's current `SPACE`, so an account the program can still load is
+/// never treated as legacy. The owning key sits at the same offset in both
+/// layouts.
+pub fn validate_legacy_account(data: &[u8], signer: &Pubkey) -> Result<()> {
+    let (offset, space, wrong_signer) = match data.get(..8) {
+        Some(d) if d == ConfessionAccount::DISCRIMINATOR => (
+            ConfessionAccount::AUTHOR_OFFSET,
+            ConfessionAccount::SPACE,
+            WhisperError::NotConfessionAuthor,
+        ),
+        Some(d) if d == CommentAccount::DISCRIMINATOR => (
+            CommentAccount::COMMENTER_OFFSET,
+            CommentAccount::SPACE,
+            WhisperError::NotCommenter,
+        ),
+        _ => return err!(WhisperError::NotLegacyAccount),
+    };
+    require!(data.len() < space, WhisperError::NotLegacyAccount);
+
+    let owner = data
+        .get(offset..offset + 32)
+        .ok_or(WhisperError::NotLegacyAccount)?;
+    if owner != signer.as_ref() {
+        return Err(wrong_signer.into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAX: usize = 200;
+
+    fn assert_rejects(uri: &str, expected: WhisperError) {
+        assert_eq!(validate_uri(uri, MAX).unwrap_err(), expected.into());
+    }
+
+    #[test]
+    fn accepts_typical_uris() {
+        validate_uri("https://arweave.net/test-hash-12345", MAX).unwrap();
+        validate_uri("ar://abc?x=1&y=2#part-2", MAX).unwrap();
+    }
+
+    #[test]
+    fn rejects_empty() {
+        assert_rejects("", WhisperError::EmptyContentUri);
+    }
+
+    #[test]
+    fn length_boundary() {
+        validate_uri(&"a".repeat(200), MAX).unwrap();
+        assert_rejects(&"a".repeat(201), WhisperError::ContentUriTooLong);
+    }
+
+    #[test]
+    fn length_counts_bytes_not_chars() {
+        // 150 characters, 250 bytes
+        let uri = format!("{}{}", "a".repeat(100), "€".repeat(50));
+        assert_eq!(uri.chars().count(), 150);
+        assert_eq!(uri.len(), 250);
+        assert_rejects(&uri, WhisperError::ContentUriTooLong);
+    }
+
+    #[test]
+    fn rejects_null_byte() {
+        assert_rejects("https://a.net/\0x", WhisperError::InvalidUriCharacter);
+    }
+
+    #[test]
+    fn rejects_whitespace() {
+        assert_rejects("https://a.net/x\ny", WhisperError::InvalidUriCharacter);
+        assert_rejects("https://a.net/x y", WhisperError::InvalidUriCharacter);
+        assert_rejects("https://a.net/x\ty", WhisperError::InvalidUriCharacter);
+    }
+
+    #[test]
+    fn rejects_non_ascii() {
+        assert_rejects("https://a.net/🙂", WhisperError::InvalidUriCharacter);
+        // Cyrillic "а" homoglyph in place of the Latin one
+        assert_rejects("https://аrweave.net/x", WhisperError::InvalidUriCharacter);
+    }
+
+    #[test]
+    fn fragment_boundary() {
+        let ok = format!("https://a.net/x#{}", "f".repeat(MAX_URI_FRAGMENT_LENGTH));
+        validate_uri(&ok, MAX).unwrap();
+
+        let long = format!(
+            "https://a.net/x#{}",
+            "f".repeat(MAX_URI_FRAGMENT_LENGTH + 1)
+        );
+        assert_rejects(&long, WhisperError::UriFragmentTooLong);
+    }
+
+    #[test]
+    fn content_warnings_accept_defined_bits() {
+        validate_content_warnings(0).unwrap();
+        validate_content_warnings(content_warning::SELF_HARM | content_warning::VIOLENCE).unwrap();
+        validate_content_warnings(content_warning::ALL).unwrap();
+    }
+
+    #[test]
+    fn content_warnings_reject_unknown_bits() {
+        for bits in [1 << 4, 1 << 15, content_warning::ALL | 1 << 8] {
+            assert_eq!(
+                validate_content_warnings(bits).unwrap_err(),
+                WhisperError::InvalidContentWarning.into()
+            );
+        }
+    }
+
+    #[test]
+    fn content_standard_accepts_known_versions_only() {
+        validate_content_standard(standards::RAW).unwrap();
+        validate_content_standard(standards::WHISPER_JSON_V1).unwrap();
+        assert_eq!(
+            validate_content_standard(standards::LATEST + 1).unwrap_err(),
+            WhisperError::UnsupportedContentStandard.into()
+        );
+    }
+
+    fn raw(discriminator: &[u8], len: usize, owner_offset: usize, owner: &Pubkey) -> Vec<u8> {
+        let mut data = vec![0; len];
+        data[..8].copy_from_slice(discriminator);
+        data[owner_offset..owner_offset + 32].copy_from_slice(owner.as_ref());
+        data
+    }
+
+    fn confession(len: usize, author: &Pubkey) -> Vec<u8> {
+        raw(ConfessionAccount::DISCRIMINATOR, len, 8, author)
+    }
+
+    fn comment(len: usize, commenter: &Pubkey) -> Vec<u8> {
+        raw(CommentAccount::DISCRIMINATOR, len, 40, commenter)
+    }
+
+    #[test]
+    fn legacy_accounts_close_for_their_owner_only() {
+        let owner = Pubkey::new_from_array([1; 32]);
+        let other = Pubkey::new_from_array([2; 32]);
+
+        let legacy = confession(ConfessionAccount::LEGACY_SPACE, &owner);
+        validate_legacy_account(&legacy, &owner).unwrap();
+        assert_eq!(
+            validate_legacy_account(&legacy, &other).unwrap_err(),
+            WhisperError::NotConfessionAuthor.into()
+        );
+
+        let legacy = comment(CommentAccount::LEGACY_SPACE, &owner);
+        validate_legacy_account(&legacy, &owner).unwrap();
+        assert_eq!(
+            validate_legacy_account(&legacy, &other).unwrap_err(),
+            WhisperError::NotCommenter.into()
+        );
+    }
+
+    #[test]
+    fn current_and_foreign_accounts_are_not_legacy() {
+        let owner = Pubkey::new_from_array([1; 32]);
+        let not_legacy = [
+            // Loads with the current program, padded or not
+            confession(ConfessionAccount::SPACE, &owner),
+            comment(CommentAccount::SPACE + 16, &owner),
+            // Some other account type
+            raw(crate::UserCounter::DISCRIMINATOR, 72, 8, &owner),
+            // Too short to hold the owner
+            comment(CommentAccount::LEGACY_SPACE, &owner)[..60].to_vec(),
+            ConfessionAccount::DISCRIMINATOR[..4].to_vec(),
+        ];
+        for data in not_legacy {
+            assert_eq!(
+                validate_legacy_account(&data, &owner).unwrap_err(),
+                WhisperError::NotLegacyAccount.into(),
+                "{} bytes",
+                data.len()
+            );
+        }
+    }
+}